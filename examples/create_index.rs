@@ -46,7 +46,7 @@ fn main() {
         client
             .query(CreateIndex::new(params))
             .map(|response| {
-                println!("{:?}", response);
+                println!("{:#}", response.resource);
             })
             .map_err(|error: faunadb::error::Error| {
                 println!("Error: {:#?}", error);
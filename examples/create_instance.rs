@@ -74,7 +74,7 @@ fn main() {
         let class_query = client.query(CreateClass::new(params));
 
         let query = class_query.and_then(|res| {
-            println!("{:?}", res);
+            println!("{:#}", res.resource);
             instance_query
         });
 
@@ -86,7 +86,7 @@ fn main() {
     tokio::run(lazy(move || {
         query
             .map(|response| {
-                println!("{:?}", response);
+                println!("{:#}", response.resource);
             })
             .map_err(|error: faunadb::error::Error| {
                 println!("Error: {:#?}", error);
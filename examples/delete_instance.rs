@@ -43,7 +43,7 @@ fn main() {
         client
             .query(query)
             .map(|response| {
-                println!("{:?}", response);
+                println!("{:#}", response.resource);
             })
             .map_err(|error: faunadb::error::Error| {
                 println!("Error: {:?}", error);
@@ -37,7 +37,7 @@ fn main() {
         client
             .query(CreateDatabase::new(params))
             .map(|response| {
-                println!("{:#?}", response);
+                println!("{:#}", response.resource);
             })
             .map_err(|error: faunadb::error::Error| {
                 println!("Error: {:#?}", error);
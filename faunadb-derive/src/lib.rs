@@ -0,0 +1,248 @@
+//! Derive macros for the [faunadb](https://docs.rs/faunadb) crate.
+//!
+//! Enable the `derive` feature of `faunadb` and use the macros through it
+//! instead of depending on this crate directly.
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Fields, Ident, Lit, Meta, NestedMeta,
+};
+
+/// Implements `faunadb::FaunaDocument` for a struct with named fields.
+///
+/// The class name defaults to the struct name in lowercase, and can be set
+/// with `#[fauna(class = "name")]`. See the `faunadb::document` module for
+/// the field attributes.
+#[proc_macro_derive(FaunaDocument, attributes(fauna))]
+pub fn derive_fauna_document(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    Data,
+    Nested,
+    Credentials,
+    Skip,
+    Reference,
+    Timestamp,
+}
+
+struct Field {
+    ident: Ident,
+    key: String,
+    kind: FieldKind,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let class = container_class(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields
+                .named
+                .iter()
+                .map(|field| {
+                    let ident = field.ident.clone().unwrap();
+                    let (key, kind) = field_attributes(&ident, &field.attrs)?;
+
+                    Ok(Field { ident, key, kind })
+                })
+                .collect::<syn::Result<Vec<_>>>()?,
+            _ => return Err(unsupported(name)),
+        },
+        _ => return Err(unsupported(name)),
+    };
+
+    let data_inserts = fields.iter().filter_map(|field| {
+        let ident = &field.ident;
+        let key = &field.key;
+
+        match field.kind {
+            FieldKind::Data => Some(quote! {
+                data.insert(#key, ::faunadb::to_expr(&self.#ident)?);
+            }),
+            FieldKind::Nested => Some(quote! {
+                data.insert(#key, ::faunadb::FaunaDocument::to_data(&self.#ident)?);
+            }),
+            _ => None,
+        }
+    });
+
+    let credential_fields: Vec<_> = fields
+        .iter()
+        .filter(|field| field.kind == FieldKind::Credentials)
+        .collect();
+
+    let to_credentials = if credential_fields.is_empty() {
+        quote!()
+    } else {
+        let inserts = credential_fields.iter().map(|field| {
+            let ident = &field.ident;
+            let key = &field.key;
+
+            quote! {
+                credentials.insert(#key, ::faunadb::to_expr(&self.#ident)?);
+            }
+        });
+
+        quote! {
+            fn to_credentials(
+                &self,
+            ) -> ::faunadb::Result<::std::option::Option<::faunadb::document::__private::Object<'static>>> {
+                let mut credentials = ::faunadb::document::__private::Object::default();
+                #(#inserts)*
+                ::std::result::Result::Ok(::std::option::Option::Some(credentials))
+            }
+        }
+    };
+
+    let from_data = field_initializers(&fields, false);
+    let from_resource = field_initializers(&fields, true);
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::faunadb::FaunaDocument for #name #ty_generics #where_clause {
+            const CLASS: &'static str = #class;
+
+            fn to_data(&self) -> ::faunadb::Result<::faunadb::document::__private::Object<'static>> {
+                let mut data = ::faunadb::document::__private::Object::default();
+                #(#data_inserts)*
+                ::std::result::Result::Ok(data)
+            }
+
+            #to_credentials
+
+            #[allow(unused_variables)]
+            fn from_data(data: &::faunadb::client::Value) -> ::faunadb::Result<Self> {
+                ::std::result::Result::Ok(Self { #(#from_data)* })
+            }
+
+            #[allow(unused_variables)]
+            fn from_resource(resource: &::faunadb::client::Value) -> ::faunadb::Result<Self> {
+                let default_data = ::faunadb::client::Value::default();
+                let data = resource.get("data").unwrap_or(&default_data);
+
+                ::std::result::Result::Ok(Self { #(#from_resource)* })
+            }
+        }
+    })
+}
+
+fn field_initializers(fields: &[Field], with_metadata: bool) -> Vec<TokenStream2> {
+    fields
+        .iter()
+        .map(|field| {
+            let ident = &field.ident;
+            let key = &field.key;
+
+            match field.kind {
+                FieldKind::Data => quote! {
+                    #ident: ::faunadb::document::__private::read_field(data, #key)?,
+                },
+                FieldKind::Nested => quote! {
+                    #ident: ::faunadb::document::__private::read_nested(data, #key)?,
+                },
+                FieldKind::Reference if with_metadata => quote! {
+                    #ident: ::faunadb::document::__private::read_field(resource, "ref")?,
+                },
+                FieldKind::Timestamp if with_metadata => quote! {
+                    #ident: ::faunadb::document::__private::read_field(resource, "ts")?,
+                },
+                _ => quote! {
+                    #ident: ::std::default::Default::default(),
+                },
+            }
+        })
+        .collect()
+}
+
+fn container_class(input: &DeriveInput) -> syn::Result<String> {
+    let mut class = None;
+
+    for meta in fauna_meta(&input.attrs)? {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.is_ident("class") => {
+                class = Some(string_literal(&nv.lit)?);
+            }
+            other => return Err(Error::new_spanned(other, "unknown fauna attribute")),
+        }
+    }
+
+    Ok(class.unwrap_or_else(|| input.ident.to_string().to_lowercase()))
+}
+
+fn field_attributes(ident: &Ident, attrs: &[Attribute]) -> syn::Result<(String, FieldKind)> {
+    let mut key = ident.to_string();
+    let mut kind = FieldKind::Data;
+
+    for meta in fauna_meta(attrs)? {
+        let new_kind = match meta {
+            NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.is_ident("rename") => {
+                key = string_literal(&nv.lit)?;
+                continue;
+            }
+            NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("skip") => FieldKind::Skip,
+            NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("nested") => FieldKind::Nested,
+            NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("credentials") => {
+                FieldKind::Credentials
+            }
+            NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("reference") => {
+                FieldKind::Reference
+            }
+            NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("ts") => FieldKind::Timestamp,
+            other => return Err(Error::new_spanned(other, "unknown fauna attribute")),
+        };
+
+        if kind != FieldKind::Data {
+            return Err(Error::new_spanned(
+                ident,
+                "only one of skip, nested, credentials, reference and ts is allowed",
+            ));
+        }
+
+        kind = new_kind;
+    }
+
+    Ok((key, kind))
+}
+
+fn fauna_meta(attrs: &[Attribute]) -> syn::Result<Vec<NestedMeta>> {
+    let mut nested = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("fauna")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => nested.extend(list.nested),
+            other => return Err(Error::new_spanned(other, "expected #[fauna(...)]")),
+        }
+    }
+
+    Ok(nested)
+}
+
+fn string_literal(lit: &Lit) -> syn::Result<String> {
+    match lit {
+        Lit::Str(s) => Ok(s.value()),
+        _ => Err(Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn unsupported(name: &Ident) -> Error {
+    Error::new(
+        Span::call_site(),
+        format!(
+            "FaunaDocument can only be derived for structs with named fields, not `{}`",
+            name
+        ),
+    )
+}
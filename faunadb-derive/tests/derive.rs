@@ -0,0 +1,190 @@
+use faunadb::{prelude::*, FaunaDocument};
+use serde_json::json;
+
+#[derive(FaunaDocument, Debug, PartialEq)]
+struct Address {
+    street: String,
+    #[fauna(rename = "zip_code")]
+    zip: String,
+}
+
+#[derive(FaunaDocument, Debug, PartialEq)]
+#[fauna(class = "users")]
+struct User {
+    #[fauna(reference)]
+    id: Option<Ref<'static>>,
+    #[fauna(ts)]
+    ts: Option<i64>,
+    #[fauna(rename = "user_name")]
+    name: String,
+    age: u32,
+    nicknames: Vec<String>,
+    #[fauna(credentials)]
+    password: String,
+    #[fauna(nested)]
+    address: Address,
+    #[fauna(skip)]
+    cached: Option<String>,
+}
+
+fn user() -> User {
+    User {
+        id: None,
+        ts: None,
+        name: String::from("Musti"),
+        age: 7,
+        nicknames: vec![String::from("mustu")],
+        password: String::from("meowmeow"),
+        address: Address {
+            street: String::from("Kissakatu 1"),
+            zip: String::from("00100"),
+        },
+        cached: Some(String::from("cache")),
+    }
+}
+
+fn expected_data() -> serde_json::Value {
+    json!({
+        "object": {
+            "user_name": "Musti",
+            "age": 7,
+            "nicknames": ["mustu"],
+            "address": {
+                "object": {
+                    "street": "Kissakatu 1",
+                    "zip_code": "00100"
+                }
+            }
+        }
+    })
+}
+
+#[test]
+fn test_class_name() {
+    assert_eq!("users", User::CLASS);
+    assert_eq!("address", Address::CLASS);
+}
+
+#[test]
+fn test_create() {
+    let query = Query::from(user().create().unwrap());
+    let serialized = serde_json::to_value(&query).unwrap();
+
+    let expected = json!({
+        "create": {
+            "@ref": {
+                "class": { "@ref": { "id": "classes" } },
+                "id": "users"
+            }
+        },
+        "params": {
+            "object": {
+                "data": expected_data(),
+                "credentials": {
+                    "object": { "password": "meowmeow" }
+                }
+            }
+        }
+    });
+
+    assert_eq!(expected, serialized);
+}
+
+#[test]
+fn test_replace() {
+    let query = Query::from(user().replace(Ref::instance("1")).unwrap());
+    let serialized = serde_json::to_value(&query).unwrap();
+
+    let expected = json!({
+        "replace": { "@ref": { "id": "1" } },
+        "params": {
+            "object": {
+                "data": expected_data(),
+                "credentials": {
+                    "object": { "password": "meowmeow" }
+                }
+            }
+        }
+    });
+
+    assert_eq!(expected, serialized);
+}
+
+#[test]
+fn test_update() {
+    let query = Query::from(user().address.update(Ref::instance("1")).unwrap());
+    let serialized = serde_json::to_value(&query).unwrap();
+
+    let expected = json!({
+        "update": { "@ref": { "id": "1" } },
+        "params": {
+            "object": {
+                "data": {
+                    "object": {
+                        "street": "Kissakatu 1",
+                        "zip_code": "00100"
+                    }
+                }
+            }
+        }
+    });
+
+    assert_eq!(expected, serialized);
+}
+
+#[test]
+fn test_from_resource() {
+    let resource: Value = serde_json::from_value(json!({
+        "ref": {
+            "@ref": {
+                "class": {
+                    "@ref": {
+                        "class": { "@ref": { "id": "classes" } },
+                        "id": "users"
+                    }
+                },
+                "id": "1"
+            }
+        },
+        "ts": 1_568_724_060_000_000i64,
+        "data": {
+            "user_name": "Musti",
+            "age": 7,
+            "nicknames": ["mustu"],
+            "address": {
+                "street": "Kissakatu 1",
+                "zip_code": "00100"
+            }
+        }
+    }))
+    .unwrap();
+
+    let read = User::from_resource(&resource).unwrap();
+
+    let mut reference = Ref::instance("1");
+    reference.set_class("users");
+
+    let mut expected = user();
+    expected.id = Some(reference);
+    expected.ts = Some(1_568_724_060_000_000);
+    expected.password = String::new();
+    expected.cached = None;
+
+    assert_eq!(expected, read);
+}
+
+#[test]
+fn test_error_path() {
+    let data: Value = serde_json::from_value(json!({
+        "user_name": "Musti",
+        "age": 7,
+        "nicknames": ["mustu"],
+        "address": { "street": 1, "zip_code": "00100" }
+    }))
+    .unwrap();
+
+    match User::from_data(&data) {
+        Err(faunadb::error::Error::DecodeError(e)) => assert_eq!("/address/street", e.path()),
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+}
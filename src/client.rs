@@ -1,10 +1,13 @@
 //! Tools for communicating with Fauna.
 
+mod pagination;
+mod redact;
 mod response;
 
 #[cfg(feature = "sync_client")]
 mod sync;
 
+pub use pagination::*;
 pub use response::*;
 
 #[cfg(feature = "sync_client")]
@@ -12,23 +15,38 @@ pub use sync::*;
 
 use crate::{
     error::{Error, FaunaErrors},
-    expr::Expr,
+    expr::{Expr, IntoOwned},
 };
 use futures::{future, stream::Stream, Future};
 use http::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
 use hyper::{client::HttpConnector, Body, StatusCode, Uri};
 use hyper_tls::HttpsConnector;
+use serde::de::DeserializeOwned;
 use serde_json;
-use std::{borrow::Cow, time::Duration};
+use std::{
+    borrow::Cow,
+    fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 use tokio_timer::Timeout;
+use zeroize::{Zeroize, Zeroizing};
 
 type Transport = hyper::Client<HttpsConnector<HttpConnector>>;
 
+/// A callback for fetching a fresh secret when Fauna rejects the current one.
+/// Returning `None` gives up and passes the `Unauthorized` error to the caller.
+///
+/// The callback runs inside the future handling the response, so it must not
+/// block. Fetch the secret in the background and return the latest one here.
+pub type SecretProvider = dyn Fn() -> Option<String> + Send + Sync;
+
 /// For building a new Fauna client.
 pub struct ClientBuilder<'a> {
     uri: Cow<'a, str>,
     secret: Cow<'a, str>,
     timeout: Duration,
+    secret_provider: Option<Arc<SecretProvider>>,
 }
 
 impl<'a> ClientBuilder<'a> {
@@ -45,18 +63,31 @@ impl<'a> ClientBuilder<'a> {
         self
     }
 
+    /// A callback to fetch a new secret if Fauna responds with `Unauthorized`,
+    /// e.g. after a key rotation. The new secret replaces the old one for all
+    /// future requests and the failed request is retried once.
+    ///
+    /// The callback is called on the event loop and must not block, see
+    /// [SecretProvider](type.SecretProvider.html).
+    pub fn secret_provider<F>(&mut self, provider: F) -> &mut Self
+    where
+        F: Fn() -> Option<String> + Send + Sync + 'static,
+    {
+        self.secret_provider = Some(Arc::new(provider));
+        self
+    }
+
     /// Creates the client.
     pub fn build(self) -> crate::Result<Client> {
         let mut builder = hyper::Client::builder();
         builder.keep_alive(true);
 
-        let secret_b64 = base64::encode(&format!("{}:", self.secret));
-
         Ok(Client {
             transport: builder.build(HttpsConnector::new(1)?),
             uri: self.uri.parse()?,
             timeout: self.timeout,
-            authorization: format!("Basic {}", secret_b64),
+            authorization: Arc::new(RwLock::new(Client::authorization(&self.secret))),
+            secret_provider: self.secret_provider.clone(),
         })
     }
 
@@ -66,16 +97,49 @@ impl<'a> ClientBuilder<'a> {
     }
 }
 
+impl<'a> Drop for ClientBuilder<'a> {
+    fn drop(&mut self) {
+        if let Cow::Owned(ref mut secret) = self.secret {
+            secret.zeroize();
+        }
+    }
+}
+
+impl<'a> fmt::Debug for ClientBuilder<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("uri", &self.uri)
+            .field("secret", &"<redacted>")
+            .field("timeout", &self.timeout)
+            .field("secret_provider", &self.secret_provider.is_some())
+            .finish()
+    }
+}
+
 /// The client for Fauna. Should be created using the
 /// [ClientBuilder](struct.ClientBuilder.html).
 ///
 /// Do not create new clients for every request to prevent
-/// spamming Fauna servers with new connections.
+/// spamming Fauna servers with new connections. Cloning the client is cheap
+/// and the clones share the same connection pool and secret.
+#[derive(Clone)]
 pub struct Client {
     transport: Transport,
     uri: Uri,
     timeout: Duration,
-    authorization: String,
+    authorization: Arc<RwLock<Zeroizing<String>>>,
+    secret_provider: Option<Arc<SecretProvider>>,
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("uri", &self.uri)
+            .field("timeout", &self.timeout)
+            .field("authorization", &"<redacted>")
+            .field("secret_provider", &self.secret_provider.is_some())
+            .finish()
+    }
 }
 
 impl Client {
@@ -86,9 +150,25 @@ impl Client {
             uri: Cow::from("https://db.fauna.com"),
             secret: secret.into(),
             timeout: Duration::new(60, 0),
+            secret_provider: None,
         }
     }
 
+    /// Replace the secret used for authenticating the requests. Requests
+    /// already sent keep using the old secret, all requests sent after this
+    /// call, including the ones from the clones of this client, use the new
+    /// one.
+    pub fn set_secret<'a>(&self, secret: impl Into<Cow<'a, str>>) {
+        let mut secret = secret.into();
+        let authorization = Self::authorization(&secret);
+
+        if let Cow::Owned(ref mut secret) = secret {
+            secret.zeroize();
+        }
+
+        *self.authorization.write().unwrap() = authorization;
+    }
+
     /// Send a query to Fauna servers and parsing the response.
     pub fn query<'a, Q>(&self, query: Q) -> FutureResponse<Response>
     where
@@ -97,13 +177,79 @@ impl Client {
         let query = query.into();
         let payload_json = serde_json::to_string(&query).unwrap();
 
-        trace!("Querying with: {:?}", &payload_json);
+        if log_enabled!(log::Level::Trace) {
+            trace!("Querying with: {}", redact::redact_query(&payload_json));
+        }
+
+        let client = self.clone();
+        let retry_payload = payload_json.clone();
+
+        let requesting = self.send_query(payload_json).or_else(move |e| match e {
+            Error::Unauthorized if client.refresh_secret() => {
+                future::Either::A(client.send_query(retry_payload))
+            }
+            e => future::Either::B(future::err(e)),
+        });
+
+        FutureResponse(Box::new(requesting))
+    }
+
+    /// Send a query to Fauna servers and read the resulting resource into the
+    /// given type. See [from_value](../fn.from_value.html) for the
+    /// supported conversions.
+    pub fn query_as<'a, T, Q>(&self, query: Q) -> FutureResponse<T>
+    where
+        Q: Into<Expr<'a>>,
+        T: DeserializeOwned + Send + 'static,
+    {
+        let reading = self
+            .query(query)
+            .and_then(|response| crate::from_value(response.resource));
+
+        FutureResponse(Box::new(reading))
+    }
 
-        self.request(self.build_request(payload_json), |body| {
+    /// Paginate through all the items in the given set, `size` items per
+    /// page. Returns a stream of pages, fetching a new page from Fauna when
+    /// the previous page is consumed.
+    pub fn paginate<'a, S>(&self, set: S, size: u32) -> PageStream
+    where
+        S: Into<Expr<'a>>,
+    {
+        PageStream::new(self.clone(), set.into().into_owned(), size)
+    }
+
+    fn send_query(&self, payload: String) -> FutureResponse<Response> {
+        self.request(self.build_request(payload), |body| {
             serde_json::from_str(&body).unwrap()
         })
     }
 
+    /// Asks a new secret from the provider, returning `true` if the secret was
+    /// replaced.
+    fn refresh_secret(&self) -> bool {
+        match self
+            .secret_provider
+            .as_ref()
+            .and_then(|provider| provider())
+            .map(Zeroizing::new)
+        {
+            Some(secret) => {
+                debug!("Got unauthorized response, retrying with a new secret");
+                self.set_secret(secret.as_str());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn authorization(secret: &str) -> Zeroizing<String> {
+        let credentials = Zeroizing::new(format!("{}:", secret));
+        let encoded = Zeroizing::new(base64::encode(credentials.as_bytes()));
+
+        Zeroizing::new(format!("Basic {}", encoded.as_str()))
+    }
+
     fn request<F, T>(&self, request: hyper::Request<Body>, f: F) -> FutureResponse<T>
     where
         T: Send + Sync + 'static,
@@ -126,7 +272,9 @@ impl Client {
 
             get_body.and_then(move |body_chunk| {
                 if let Ok(body) = String::from_utf8(body_chunk.to_vec()) {
-                    trace!("Got response: {:?}", &body);
+                    if log_enabled!(log::Level::Trace) {
+                        trace!("Got response: {}", redact::redact(&body));
+                    }
 
                     match status {
                         s if s.is_success() => future::ok(f(body)),
@@ -169,9 +317,65 @@ impl Client {
 
         builder.header(CONTENT_LENGTH, format!("{}", payload.len()).as_bytes());
         builder.header(CONTENT_TYPE, "application/json");
-        builder.header(AUTHORIZATION, self.authorization.as_bytes());
+        builder.header(AUTHORIZATION, self.authorization.read().unwrap().as_bytes());
         builder.header("X-FaunaDB-API-Version", "2.1");
 
         builder.body(Body::from(payload)).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authorization_header(client: &Client) -> String {
+        let request = client.build_request(String::new());
+        let header = request.headers().get(AUTHORIZATION).unwrap();
+
+        header.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_set_secret() {
+        let client = Client::builder("old").build().unwrap();
+        let cloned = client.clone();
+
+        assert_eq!("Basic b2xkOg==", authorization_header(&client));
+
+        client.set_secret("new");
+
+        assert_eq!("Basic bmV3Og==", authorization_header(&client));
+        assert_eq!("Basic bmV3Og==", authorization_header(&cloned));
+    }
+
+    #[test]
+    fn test_secret_provider() {
+        let mut builder = Client::builder("old");
+        builder.secret_provider(|| Some(String::from("new")));
+
+        let client = builder.build().unwrap();
+
+        assert!(client.refresh_secret());
+        assert_eq!("Basic bmV3Og==", authorization_header(&client));
+    }
+
+    #[test]
+    fn test_debug_hides_secret() {
+        let builder = Client::builder(String::from("fnAD"));
+        assert!(!format!("{:?}", builder).contains("fnAD"));
+
+        let client = builder.build().unwrap();
+        let debugged = format!("{:?}", client);
+
+        assert!(!debugged.contains("Basic"));
+        assert!(debugged.contains("<redacted>"));
+    }
+
+    #[test]
+    fn test_no_secret_provider() {
+        let client = Client::builder("old").build().unwrap();
+
+        assert!(!client.refresh_secret());
+        assert_eq!("Basic b2xkOg==", authorization_header(&client));
+    }
+}
@@ -0,0 +1,268 @@
+use super::{Client, Cursor, FutureResponse, Page, Response, Value};
+use crate::{
+    error::Error,
+    expr::{Expr, IntoOwned},
+    query::{collection::Map, read::Paginate},
+};
+use futures::{try_ready, Async, Future, Poll, Stream};
+use std::{mem, vec};
+
+/// A stream of pages, fetching the next page from Fauna until the set is
+/// exhausted. Should be created with
+/// [Client::paginate](struct.Client.html#method.paginate).
+///
+/// ```no_run
+/// # use faunadb::prelude::*;
+/// # use futures::{Future, Stream};
+/// #
+/// let client = Client::builder("my_fauna_secret").build().unwrap();
+///
+/// let mut pages = client.paginate(Match::new(Index::find("all_cats")), 100);
+/// pages.mapper(Lambda::new("cat", Get::instance(Var::new("cat"))));
+///
+/// let all_cats = pages.items().collect().wait().unwrap();
+/// ```
+pub struct PageStream {
+    client: Client,
+    set: Expr<'static>,
+    size: u32,
+    reverse: bool,
+    timestamp: Option<Expr<'static>>,
+    mapper: Option<Expr<'static>>,
+    state: PageState,
+}
+
+enum PageState {
+    Waiting(Option<Cursor>),
+    Fetching(FutureResponse<Response>),
+    Done,
+}
+
+impl PageStream {
+    pub(crate) fn new(client: Client, set: Expr<'static>, size: u32) -> Self {
+        Self {
+            client,
+            set,
+            size,
+            reverse: false,
+            timestamp: None,
+            mapper: None,
+            state: PageState::Waiting(None),
+        }
+    }
+
+    /// Traverse the set from the end to the beginning, following the `before`
+    /// cursors.
+    pub fn reverse(&mut self) -> &mut Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Read all the pages from the set at the given point in time, so changes
+    /// done during the traversal are not visible.
+    pub fn timestamp<'a>(&mut self, timestamp: impl Into<Expr<'a>>) -> &mut Self {
+        self.timestamp = Some(timestamp.into().into_owned());
+        self
+    }
+
+    /// A `Lambda` applied to every item of every page in Fauna, e.g. for
+    /// getting the instances of the paginated refs.
+    pub fn mapper<'a>(&mut self, lambda: impl Into<Expr<'a>>) -> &mut Self {
+        self.mapper = Some(lambda.into().into_owned());
+        self
+    }
+
+    /// Transforms the stream of pages into a stream of the items in the pages.
+    /// If traversing in reverse, the items are in reversed order.
+    pub fn items(self) -> ItemStream {
+        ItemStream {
+            pages: self,
+            buffer: Vec::new().into_iter(),
+        }
+    }
+
+    fn query(&self, cursor: Option<Cursor>) -> Expr<'static> {
+        let mut paginate = Paginate::new(self.set.clone());
+        paginate.size(self.size);
+
+        if let Some(ref timestamp) = self.timestamp {
+            paginate.timestamp(timestamp.clone());
+        }
+
+        match (self.reverse, cursor) {
+            (false, Some(cursor)) => {
+                paginate.after(cursor);
+            }
+            (true, Some(cursor)) => {
+                paginate.before(cursor);
+            }
+            (true, None) => {
+                paginate.before(Expr::null());
+            }
+            (false, None) => (),
+        }
+
+        match self.mapper {
+            Some(ref lambda) => Expr::from(Map::new(paginate, lambda.clone())),
+            None => Expr::from(paginate),
+        }
+    }
+}
+
+impl Stream for PageStream {
+    type Item = Page;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            match mem::replace(&mut self.state, PageState::Done) {
+                PageState::Waiting(cursor) => {
+                    let query = self.query(cursor);
+                    self.state = PageState::Fetching(self.client.query(query));
+                }
+                PageState::Fetching(mut fetching) => {
+                    let response = match fetching.poll()? {
+                        Async::Ready(response) => response,
+                        Async::NotReady => {
+                            self.state = PageState::Fetching(fetching);
+                            return Ok(Async::NotReady);
+                        }
+                    };
+
+                    let page: Page = Page::from_value(response.resource)?;
+
+                    let next = if self.reverse {
+                        page.before.clone()
+                    } else {
+                        page.after.clone()
+                    };
+
+                    if next.is_some() {
+                        self.state = PageState::Waiting(next);
+                    }
+
+                    return Ok(Async::Ready(Some(page)));
+                }
+                PageState::Done => return Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+/// A stream of items from all the pages of a set. Created with
+/// [PageStream::items](struct.PageStream.html#method.items).
+pub struct ItemStream {
+    pages: PageStream,
+    buffer: vec::IntoIter<Value>,
+}
+
+impl Stream for ItemStream {
+    type Item = Value;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(item) = self.buffer.next() {
+                return Ok(Async::Ready(Some(item)));
+            }
+
+            match try_ready!(self.pages.poll()) {
+                Some(mut page) => {
+                    if self.pages.reverse {
+                        page.data.reverse();
+                    }
+
+                    self.buffer = page.data.into_iter();
+                }
+                None => return Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use chrono::{offset::TimeZone, Utc};
+    use serde_json::{self, json};
+
+    fn pages() -> PageStream {
+        let client = Client::builder("secret").build().unwrap();
+        client.paginate(Match::new(Index::find("all_cats")), 10)
+    }
+
+    fn cursor() -> Cursor {
+        Cursor::from(Value::from(vec![Value::from(1)]))
+    }
+
+    #[test]
+    fn test_first_page() {
+        let query = pages().query(None);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "paginate": { "match": { "index": "all_cats" } },
+            "size": 10,
+            "sources": false,
+            "events": false,
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_next_page() {
+        let query = pages().query(Some(cursor()));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "paginate": { "match": { "index": "all_cats" } },
+            "after": [1],
+            "size": 10,
+            "sources": false,
+            "events": false,
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_reverse_pages() {
+        let mut pages = pages();
+        pages.reverse();
+
+        let first = serde_json::to_value(&pages.query(None)).unwrap();
+        let next = serde_json::to_value(&pages.query(Some(cursor()))).unwrap();
+
+        assert_eq!(json!(null), first["before"]);
+        assert_eq!(json!([1]), next["before"]);
+        assert_eq!(None, next.get("after"));
+    }
+
+    #[test]
+    fn test_pinned_pages_with_mapper() {
+        let mut pages = pages();
+        pages.timestamp(Utc.timestamp(60, 0));
+        pages.mapper(Lambda::new("cat", Get::instance(Var::new("cat"))));
+
+        let query = pages.query(Some(cursor()));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "map": {
+                "lambda": "cat",
+                "expr": { "get": { "var": "cat" } }
+            },
+            "collection": {
+                "paginate": { "match": { "index": "all_cats" } },
+                "ts": { "@ts": "1970-01-01T00:01:00Z" },
+                "after": [1],
+                "size": 10,
+                "sources": false,
+                "events": false,
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+}
@@ -0,0 +1,123 @@
+//! Hiding secrets from the logged queries and responses.
+use crate::{client::Value, fql::Node};
+use serde_json::Value as Json;
+
+/// Keys holding data that should never end up in the logs.
+const SECRET_KEYS: &[&str] = &[
+    "password",
+    "credentials",
+    "secret",
+    "hashed_secret",
+    "key_from_secret",
+];
+
+const REDACTED: &str = "<redacted>";
+
+/// Replaces the values of password, credential and secret fields in the given
+/// JSON payload. Payloads that cannot be parsed as JSON are hidden completely.
+pub(crate) fn redact(payload: &str) -> String {
+    match serde_json::from_str::<Json>(payload) {
+        Ok(mut json) => {
+            redact_json(&mut json);
+            json.to_string()
+        }
+        Err(_) => String::from(REDACTED),
+    }
+}
+
+/// Like `redact`, rendering the query payload in FQL.
+pub(crate) fn redact_query(payload: &str) -> String {
+    let value = serde_json::from_str::<Json>(payload).and_then(|mut json| {
+        redact_json(&mut json);
+        serde_json::from_value::<Value>(json)
+    });
+
+    match value {
+        Ok(value) => Node::from_query(&value).flat(),
+        Err(_) => String::from(REDACTED),
+    }
+}
+
+fn redact_json(json: &mut Json) {
+    match json {
+        Json::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if SECRET_KEYS.contains(&key.as_str()) {
+                    *value = Json::from(REDACTED);
+                } else {
+                    redact_json(value);
+                }
+            }
+        }
+        Json::Array(values) => values.iter_mut().for_each(redact_json),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    #[test]
+    fn test_redact_login() {
+        let query = Expr::from(Login::new(Ref::instance("musti"), "Hunter2"));
+        let payload = serde_json::to_string(&query).unwrap();
+        let redacted: Json = serde_json::from_str(&redact(&payload)).unwrap();
+
+        let expected = json!({
+            "login": { "@ref": { "id": "musti" } },
+            "params": { "object": { "password": "<redacted>" } }
+        });
+
+        assert_eq!(expected, redacted);
+    }
+
+    #[test]
+    fn test_redact_query() {
+        let query = Expr::from(Login::new(Ref::instance("musti"), "Hunter2"));
+        let payload = serde_json::to_string(&query).unwrap();
+
+        assert_eq!(
+            "Login(Ref(\"musti\"), { password: \"<redacted>\" })",
+            redact_query(&payload)
+        );
+    }
+
+    #[test]
+    fn test_redact_credentials() {
+        let mut credentials = Object::default();
+        credentials.insert("password", "Hunter2");
+
+        let mut params = UpdateParams::new();
+        params.credentials(credentials);
+
+        let query = Expr::from(Update::new(Ref::instance("musti"), params));
+        let payload = serde_json::to_string(&query).unwrap();
+
+        assert!(!redact(&payload).contains("Hunter2"));
+    }
+
+    #[test]
+    fn test_redact_response() {
+        let body =
+            r#"{"resource":{"ref":{"@ref":{"id":"1"}},"secret":"fnAD","hashed_secret":"$2a"}}"#;
+        let redacted: Json = serde_json::from_str(&redact(body)).unwrap();
+
+        let expected = json!({
+            "resource": {
+                "ref": { "@ref": { "id": "1" } },
+                "secret": "<redacted>",
+                "hashed_secret": "<redacted>",
+            }
+        });
+
+        assert_eq!(expected, redacted);
+    }
+
+    #[test]
+    fn test_redact_invalid_json() {
+        assert_eq!("<redacted>", redact("secret: fnAD"));
+    }
+}
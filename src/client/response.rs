@@ -1,10 +1,23 @@
+mod cursor;
+mod document;
+mod event;
 mod index;
+mod page;
+mod path;
+mod set_ref;
 mod value;
 
 use crate::error::Error;
+use chrono::{DateTime, TimeZone, Utc};
 use futures::{Future, Poll};
 
+pub use cursor::*;
+pub use document::*;
+pub use event::*;
 pub use index::*;
+pub use page::*;
+pub use path::*;
+pub use set_ref::*;
 pub use value::*;
 
 pub struct FutureResponse<T>(pub Box<dyn Future<Item = T, Error = Error> + Send + 'static>);
@@ -22,3 +35,12 @@ impl<T> Future for FutureResponse<T> {
 pub struct Response {
     pub resource: Value,
 }
+
+/// Converts a Fauna `ts` field, microseconds since the Unix epoch, to a
+/// timestamp. `None` if the value is out of the range `chrono` supports.
+pub(crate) fn micros_to_timestamp(micros: i64) -> Option<DateTime<Utc>> {
+    let secs = micros.div_euclid(1_000_000);
+    let nanos = micros.rem_euclid(1_000_000) * 1000;
+
+    Utc.timestamp_opt(secs, nanos as u32).single()
+}
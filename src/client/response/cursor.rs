@@ -0,0 +1,216 @@
+use super::Value;
+use crate::{error::Error, expr::Expr};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// A pagination cursor, to be used with `Paginate::after` and
+/// `Paginate::before` for fetching the next or the previous page.
+///
+/// A cursor can be encoded into an opaque, URL-safe token for passing it to
+/// clients outside of the application, and decoded back when the client asks
+/// for the next page. Use a [CursorSigner](struct.CursorSigner.html) if the
+/// clients should not be able to modify the cursors.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// #
+/// let cursor = Cursor::from(Value::from(vec!["musti"]));
+/// let token = cursor.encode();
+///
+/// assert_eq!(cursor, Cursor::decode(&token).unwrap());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Cursor(Value);
+
+/// Encodes cursors into signed tokens, and decodes the tokens back into
+/// cursors if the signature matches.
+///
+/// The signature is a HMAC-SHA256 of the encoded cursor, keeping the cursor
+/// itself readable to anybody holding the token.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// #
+/// let signer = CursorSigner::new("my_signing_key");
+///
+/// let cursor = Cursor::from(Value::from(vec!["musti"]));
+/// let token = signer.encode(&cursor);
+///
+/// assert_eq!(cursor, signer.decode(&token).unwrap());
+/// assert!(CursorSigner::new("other_key").decode(&token).is_err());
+/// ```
+#[derive(Clone)]
+pub struct CursorSigner {
+    key: Vec<u8>,
+}
+
+impl Cursor {
+    /// The cursor value as returned from Fauna.
+    pub fn as_value(&self) -> &Value {
+        &self.0
+    }
+
+    /// Transforms the cursor into the value returned from Fauna.
+    pub fn into_value(self) -> Value {
+        self.0
+    }
+
+    /// Encodes the cursor into an URL-safe string.
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(&self.0).unwrap();
+        base64::encode_config(&json, base64::URL_SAFE_NO_PAD)
+    }
+
+    /// Decodes a string created with `Cursor::encode` back into a cursor.
+    pub fn decode(token: &str) -> crate::Result<Self> {
+        let json = base64::decode_config(token, base64::URL_SAFE_NO_PAD)
+            .map_err(|_| Error::ConversionError("Cursor token is not valid base64"))?;
+
+        let value = serde_json::from_slice(&json)
+            .map_err(|_| Error::ConversionError("Cursor token is not a valid cursor"))?;
+
+        Ok(Cursor(value))
+    }
+}
+
+impl CursorSigner {
+    /// A new signer using the given secret key.
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: key.as_ref().to_vec(),
+        }
+    }
+
+    /// Encodes the cursor into an URL-safe string with a signature.
+    pub fn encode(&self, cursor: &Cursor) -> String {
+        let payload = cursor.encode();
+        let signature = self.mac(&payload).result().code();
+
+        format!(
+            "{}.{}",
+            payload,
+            base64::encode_config(&signature, base64::URL_SAFE_NO_PAD)
+        )
+    }
+
+    /// Decodes a string created with `CursorSigner::encode` back into a
+    /// cursor, failing if the token was not signed with the same key.
+    pub fn decode(&self, token: &str) -> crate::Result<Cursor> {
+        let mut parts = token.splitn(2, '.');
+        let payload = parts.next().unwrap_or("");
+
+        let signature = parts
+            .next()
+            .and_then(|sig| base64::decode_config(sig, base64::URL_SAFE_NO_PAD).ok())
+            .ok_or(Error::ConversionError("Cursor token is not signed"))?;
+
+        self.mac(payload)
+            .verify(&signature)
+            .map_err(|_| Error::ConversionError("Cursor token signature does not match"))?;
+
+        Cursor::decode(payload)
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_varkey(&self.key).unwrap();
+        mac.input(payload.as_bytes());
+        mac
+    }
+}
+
+impl From<Value> for Cursor {
+    fn from(value: Value) -> Self {
+        Cursor(value)
+    }
+}
+
+impl<'a> From<Cursor> for Expr<'a> {
+    fn from(cursor: Cursor) -> Self {
+        Expr::from(cursor.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use chrono::{offset::TimeZone, NaiveDate, Utc};
+    use serde_json::{self, json};
+
+    fn cursor() -> Cursor {
+        let value: Value = serde_json::from_value(json!([
+            {"@ref": {"id": "1", "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}}},
+            {"@ts": "2019-05-26T16:20:00.123456789Z"},
+            {"@date": "2019-05-26"},
+            {"@bytes": "AQID"},
+            {"@set": {"match": {"@ref": {"id": "cats_by_name", "class": {"@ref": {"id": "indexes"}}}}, "terms": "Musti"}},
+            {"@query": {"lambda": "x", "expr": {"var": "x"}}},
+            {"ts": 60, "action": "add"},
+            -1,
+            2.5,
+            "Musti",
+            null,
+        ]))
+        .unwrap();
+
+        Cursor::from(value)
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let cursor = cursor();
+        let token = cursor.encode();
+
+        assert!(token
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+        let decoded = Cursor::decode(&token).unwrap();
+
+        assert_eq!(cursor, decoded);
+        assert_eq!(
+            Some(Utc.timestamp_opt(1_558_887_600, 123_456_789).unwrap()),
+            decoded.as_value()[1].as_timestamp()
+        );
+        assert_eq!(
+            Some(NaiveDate::from_ymd_opt(2019, 5, 26).unwrap()),
+            decoded.as_value()[2].as_date()
+        );
+        assert_eq!(
+            Some(&Bytes::from(vec![0x1, 0x2, 0x3])),
+            decoded.as_value()[3].as_bytes()
+        );
+        assert!(decoded.as_value()[4].is_set());
+        assert!(decoded.as_value()[5].is_query());
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert!(Cursor::decode("not a cursor").is_err());
+        assert!(Cursor::decode("bm90IGpzb24").is_err());
+    }
+
+    #[test]
+    fn test_signed() {
+        let signer = CursorSigner::new("meow");
+        let cursor = cursor();
+        let token = signer.encode(&cursor);
+
+        assert_eq!(cursor, signer.decode(&token).unwrap());
+    }
+
+    #[test]
+    fn test_signed_tampered() {
+        let signer = CursorSigner::new("meow");
+        let token = signer.encode(&cursor());
+
+        let other = Cursor::from(Value::from(vec![Value::from(1)])).encode();
+        let signature = token.split('.').nth(1).unwrap();
+        let tampered = format!("{}.{}", other, signature);
+
+        assert!(signer.decode(&tampered).is_err());
+        assert!(signer.decode(&cursor().encode()).is_err());
+        assert!(CursorSigner::new("purr").decode(&token).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use super::{event::deserialize_ref, micros_to_timestamp, Value};
+use crate::{
+    expr::{Expr, Ref},
+    query::write::{Delete, Replace, Update, UpdateParams},
+};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+
+/// An instance returned from Fauna, e.g. as a response to `Get`, with the
+/// data deserialized into `T`.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// # use serde_json::json;
+/// #
+/// #[derive(serde_derive::Deserialize)]
+/// struct Cat {
+///     name: String,
+/// }
+///
+/// let value: Value = serde_json::from_value(json!({
+///     "ref": { "@ref": { "id": "1" } },
+///     "class": { "@ref": { "id": "cats" } },
+///     "ts": 1_000_000,
+///     "data": { "name": "Musti" },
+/// })).unwrap();
+///
+/// let cat: Document<Cat> = Document::from_value(value).unwrap();
+/// assert_eq!("Musti", cat.data.name);
+///
+/// let mut data = Object::default();
+/// data.insert("name", "Naukio");
+///
+/// let mut params = UpdateParams::new();
+/// params.data(data);
+///
+/// let update = cat.update_expr(params);
+/// ```
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Document<T = Value> {
+    /// The ref of the instance.
+    #[serde(rename = "ref", deserialize_with = "deserialize_ref")]
+    pub reference: Ref<'static>,
+    /// The class of the instance.
+    #[serde(alias = "collection", deserialize_with = "deserialize_ref")]
+    pub class: Ref<'static>,
+    /// The time of the latest change, in microseconds since the Unix epoch.
+    pub ts: i64,
+    /// The instance data.
+    pub data: T,
+}
+
+impl<T> Document<T>
+where
+    T: DeserializeOwned,
+{
+    /// Reads the document from a response, such as the result of `Get`.
+    pub fn from_value(value: Value) -> crate::Result<Self> {
+        crate::from_value(value)
+    }
+}
+
+impl<T> Document<T> {
+    /// The time of the latest change, `None` if `ts` is out of the supported
+    /// range.
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        micros_to_timestamp(self.ts)
+    }
+
+    /// An `Update` query for changing this document.
+    pub fn update_expr<'a>(&self, params: UpdateParams<'a>) -> Update<'a> {
+        Update::new(self.reference.clone(), params)
+    }
+
+    /// A `Replace` query for substituting the data of this document.
+    pub fn replace_expr<'a>(&self, params: impl Into<Expr<'a>>) -> Replace<'a> {
+        Replace::new(self.reference.clone(), params)
+    }
+
+    /// A `Delete` query for removing this document.
+    pub fn delete_expr(&self) -> Delete<'static> {
+        Delete::new(self.reference.clone())
+    }
+
+    /// Transforms the data, keeping the metadata.
+    pub fn map<U, F>(self, f: F) -> Document<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Document {
+            reference: self.reference,
+            class: self.class,
+            ts: self.ts,
+            data: f(self.data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use chrono::{offset::TimeZone, Utc};
+    use serde_json::{self, json};
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Cat {
+        name: String,
+        age: u32,
+    }
+
+    fn response() -> Value {
+        serde_json::from_value(json!({
+            "ref": {
+                "@ref": {
+                    "class": {
+                        "@ref": {
+                            "class": { "@ref": { "id": "classes" } },
+                            "id": "cats"
+                        }
+                    },
+                    "id": "1"
+                }
+            },
+            "class": {
+                "@ref": {
+                    "class": { "@ref": { "id": "classes" } },
+                    "id": "cats"
+                }
+            },
+            "ts": 60_000_001,
+            "data": { "name": "Musti", "age": 7 }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_value() {
+        let doc: Document<Cat> = Document::from_value(response()).unwrap();
+
+        let mut reference = Ref::instance("1");
+        reference.set_class("cats");
+
+        assert_eq!(reference, doc.reference);
+        assert_eq!(Ref::class("cats"), doc.class);
+        assert_eq!(60_000_001, doc.ts);
+        assert_eq!(Utc.timestamp_opt(60, 1000).single(), doc.timestamp());
+
+        assert_eq!(
+            Cat {
+                name: String::from("Musti"),
+                age: 7
+            },
+            doc.data
+        );
+    }
+
+    #[test]
+    fn test_from_json() {
+        let doc: Document = serde_json::from_value(json!({
+            "ref": { "@ref": { "id": "1" } },
+            "collection": { "@ref": { "id": "cats" } },
+            "ts": 1,
+            "data": { "name": "Musti" }
+        }))
+        .unwrap();
+
+        assert_eq!(Ref::instance("1"), doc.reference);
+        assert_eq!(Some("Musti"), doc.data["name"].as_str());
+    }
+
+    #[test]
+    fn test_missing_ref() {
+        let value: Value = serde_json::from_value(json!({
+            "ts": 1,
+            "data": { "name": "Musti" }
+        }))
+        .unwrap();
+
+        assert!(Document::<Value>::from_value(value).is_err());
+    }
+
+    #[test]
+    fn test_update_expr() {
+        let doc: Document = Document::from_value(response()).unwrap();
+
+        let mut data = Object::default();
+        data.insert("age", 8);
+
+        let mut params = UpdateParams::new();
+        params.data(data);
+
+        let query = Query::from(doc.update_expr(params));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json!("1"), serialized["update"]["@ref"]["id"]);
+        assert_eq!(
+            json!({ "object": { "data": { "object": { "age": 8 } } } }),
+            serialized["params"]
+        );
+    }
+
+    #[test]
+    fn test_replace_and_delete_expr() {
+        let doc: Document = Document::from_value(response()).unwrap();
+
+        let mut data = Object::default();
+        data.insert("name", "Naukio");
+
+        let mut params = Object::default();
+        params.insert("data", data);
+
+        let replace = serde_json::to_value(&Query::from(doc.replace_expr(params))).unwrap();
+        assert_eq!(json!("1"), replace["replace"]["@ref"]["id"]);
+
+        let delete = serde_json::to_value(&Query::from(doc.delete_expr())).unwrap();
+        assert_eq!(json!("1"), delete["delete"]["@ref"]["id"]);
+    }
+}
@@ -0,0 +1,232 @@
+use super::{micros_to_timestamp, AnnotatedValue, Value};
+use crate::{
+    error::Error,
+    expr::{Expr, Ref},
+    query::write::{Action, Insert, InsertParams, Remove},
+};
+use chrono::{DateTime, Utc};
+use serde::de::{self, Deserialize, Deserializer};
+
+/// An event from the history of an instance or a set, returned when paginating
+/// with `events(true)`.
+///
+/// Read the
+/// [docs](https://docs.fauna.com/fauna/current/reference/temporality).
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Event {
+    /// The time of the event, in microseconds since the Unix epoch.
+    pub ts: i64,
+    /// The kind of the event.
+    pub action: EventAction,
+    /// The instance the event happened to.
+    #[serde(
+        alias = "document",
+        alias = "resource",
+        deserialize_with = "deserialize_ref"
+    )]
+    pub instance: Ref<'static>,
+    /// The instance data after an instance event, or the index values of a
+    /// set event.
+    #[serde(default)]
+    pub data: Option<Value>,
+}
+
+/// The kind of an `Event`. Instance events are `Create`, `Update` and `Delete`,
+/// mirroring [Action](../query/write/enum.Action.html), set events are `Add`
+/// and `Remove`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventAction {
+    #[serde(rename = "create")]
+    Create,
+    #[serde(rename = "update")]
+    Update,
+    #[serde(rename = "delete")]
+    Delete,
+    #[serde(rename = "add")]
+    Add,
+    #[serde(rename = "remove")]
+    Remove,
+}
+
+impl EventAction {
+    /// The write action of an instance event, `None` for set events.
+    pub fn to_action(self) -> Option<Action> {
+        match self {
+            EventAction::Create => Some(Action::Create),
+            EventAction::Update => Some(Action::Update),
+            EventAction::Delete => Some(Action::Delete),
+            EventAction::Add | EventAction::Remove => None,
+        }
+    }
+}
+
+impl From<Action> for EventAction {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Create => EventAction::Create,
+            Action::Update => EventAction::Update,
+            Action::Delete => EventAction::Delete,
+        }
+    }
+}
+
+impl Event {
+    /// The time of the event, `None` if `ts` is out of the supported range.
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        micros_to_timestamp(self.ts)
+    }
+
+    /// An `Insert` query to write the event back to the history of the
+    /// instance, e.g. to a copy of the instance. Only instance events can be
+    /// inserted.
+    pub fn to_insert(&self) -> crate::Result<Insert<'static>> {
+        let action = self.write_action()?;
+        let ts = self.checked_timestamp()?;
+
+        let data = match self.data {
+            Some(ref data) => Expr::from(data.clone()),
+            None => Expr::null(),
+        };
+
+        let params = InsertParams::new(data, Expr::null(), Expr::null());
+
+        Ok(Insert::new(self.instance.clone(), ts, action, params))
+    }
+
+    /// A `Remove` query to delete the event from the history of the instance.
+    /// Only instance events can be removed.
+    pub fn to_remove(&self) -> crate::Result<Remove<'static>> {
+        let action = self.write_action()?;
+        let ts = self.checked_timestamp()?;
+
+        Ok(Remove::new(self.instance.clone(), ts, action))
+    }
+
+    fn write_action(&self) -> crate::Result<Action> {
+        self.action
+            .to_action()
+            .ok_or(Error::ConversionError("Set events cannot be written"))
+    }
+
+    fn checked_timestamp(&self) -> crate::Result<DateTime<Utc>> {
+        self.timestamp()
+            .ok_or(Error::ConversionError("Event timestamp out of range"))
+    }
+}
+
+pub(super) fn deserialize_ref<'de, D>(deserializer: D) -> Result<Ref<'static>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match AnnotatedValue::deserialize(deserializer)? {
+        AnnotatedValue::Ref(reference) => Ok(reference),
+        _ => Err(de::Error::custom("expected a ref")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use chrono::{offset::TimeZone, Utc};
+    use serde_json::{self, json};
+
+    fn instance_event() -> Value {
+        serde_json::from_value(json!({
+            "ts": 60_000_001,
+            "action": "update",
+            "instance": {"@ref": {"id": "musti"}},
+            "data": {"age": 8}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_events_page() {
+        let value: Value = serde_json::from_value(json!({
+            "data": [
+                instance_event(),
+                {
+                    "ts": 60_000_002,
+                    "action": "add",
+                    "document": {"@ref": {"id": "naukio"}},
+                    "data": ["Naukio"]
+                },
+            ]
+        }))
+        .unwrap();
+
+        let page: Page<Event> = Page::from_value(value).unwrap();
+
+        assert_eq!(EventAction::Update, page.data[0].action);
+        assert_eq!("musti", page.data[0].instance.id);
+        assert_eq!(Some(8), page.data[0].data.as_ref().unwrap()["age"].as_u64());
+
+        assert_eq!(EventAction::Add, page.data[1].action);
+        assert_eq!("naukio", page.data[1].instance.id);
+        assert_eq!(None, page.data[1].action.to_action());
+    }
+
+    #[test]
+    fn test_event_timestamp() {
+        let page: Page<Event> = Page::from_value(
+            serde_json::from_value(json!({ "data": [instance_event()] })).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Utc.timestamp_opt(60, 1000).single(),
+            page.data[0].timestamp()
+        );
+
+        let mut event = page.data[0].clone();
+        event.ts = std::i64::MAX;
+
+        assert_eq!(None, event.timestamp());
+        assert!(event.to_insert().is_err());
+    }
+
+    #[test]
+    fn test_event_to_insert() {
+        let page: Page<Event> = Page::from_value(
+            serde_json::from_value(json!({ "data": [instance_event()] })).unwrap(),
+        )
+        .unwrap();
+
+        let query = Query::from(page.data[0].to_insert().unwrap());
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "insert": {"@ref": {"id": "musti"}},
+            "ts": {"@ts": "1970-01-01T00:01:00.000001Z"},
+            "action": "update",
+            "params": {
+                "object": {
+                    "data": {"object": {"age": 8}},
+                    "credentials": null,
+                    "delegates": null,
+                }
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_event_to_remove() {
+        let page: Page<Event> = Page::from_value(
+            serde_json::from_value(json!({ "data": [instance_event()] })).unwrap(),
+        )
+        .unwrap();
+
+        let query = Query::from(page.data[0].to_remove().unwrap());
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "remove": {"@ref": {"id": "musti"}},
+            "ts": {"@ts": "1970-01-01T00:01:00.000001Z"},
+            "action": "update",
+        });
+
+        assert_eq!(expected, serialized);
+    }
+}
@@ -0,0 +1,159 @@
+use super::{Cursor, Value};
+use serde::de::DeserializeOwned;
+
+/// A page of results returned from a
+/// [Paginate](../query/read/struct.Paginate.html) query.
+///
+/// The items are deserialized into `T`, being a [Value](enum.Value.html) by
+/// default. Pages from paginating with `sources(true)` can be read as
+/// `Page<Sourced>`.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// # use serde_json::json;
+/// #
+/// let value: Value = serde_json::from_value(json!({
+///     "data": [1, 2],
+///     "after": [3],
+/// })).unwrap();
+///
+/// let page: Page<u64> = Page::from_value(value).unwrap();
+/// assert_eq!(vec![1, 2], page.data);
+///
+/// let mut paginate = Paginate::new(Classes::all());
+/// paginate.after(page.after.unwrap());
+/// ```
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Page<T = Value> {
+    /// The items in the page.
+    pub data: Vec<T>,
+    /// A cursor for fetching the previous page, `None` if in the beginning of
+    /// the set.
+    #[serde(default)]
+    pub before: Option<Cursor>,
+    /// A cursor for fetching the next page, `None` if in the end of the set.
+    #[serde(default)]
+    pub after: Option<Cursor>,
+}
+
+/// An item from a page paginated with `sources(true)`, containing the sets
+/// the item was found from.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Sourced<T = Value> {
+    pub value: T,
+    pub sources: Vec<Value>,
+}
+
+impl<T> Page<T>
+where
+    T: DeserializeOwned,
+{
+    /// Reads the page from the response of a `Paginate` query.
+    pub fn from_value(value: Value) -> crate::Result<Self> {
+        crate::from_value(value)
+    }
+}
+
+impl<T> Page<T> {
+    /// `true` if there are no more pages after this page.
+    pub fn is_last(&self) -> bool {
+        self.after.is_none()
+    }
+
+    /// `true` if there are no more pages before this page.
+    pub fn is_first(&self) -> bool {
+        self.before.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use chrono::{offset::TimeZone, Utc};
+    use serde_json::{self, json};
+
+    #[test]
+    fn test_page_from_value() {
+        let value: Value = serde_json::from_value(json!({
+            "data": [
+                {"@ref": {"id": "1", "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}}},
+                {"@ref": {"id": "2", "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}}},
+            ],
+            "before": [{"@ref": {"id": "1", "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}}}],
+            "after": [{"@ref": {"id": "3", "class": {"@ref": {"id": "cats", "class": {"@ref": {"id": "classes"}}}}}}],
+        }))
+        .unwrap();
+
+        let page: Page = Page::from_value(value).unwrap();
+
+        assert_eq!(2, page.data.len());
+        assert_eq!("2", page.data[1].as_reference().unwrap().id);
+        assert!(!page.is_first());
+        assert!(!page.is_last());
+
+        let after = page.after.unwrap();
+        assert_eq!("3", after.as_value()[0].as_reference().unwrap().id);
+    }
+
+    #[test]
+    fn test_page_without_cursors() {
+        let value: Value = serde_json::from_value(json!({ "data": ["Musti"] })).unwrap();
+        let page: Page<String> = Page::from_value(value).unwrap();
+
+        assert_eq!(vec!["Musti"], page.data);
+        assert!(page.is_first());
+        assert!(page.is_last());
+    }
+
+    #[test]
+    fn test_page_with_sources() {
+        let value: Value = serde_json::from_value(json!({
+            "data": [{
+                "value": "Musti",
+                "sources": [{"@set": {"match": {"@ref": {"id": "cats_by_name", "class": {"@ref": {"id": "indexes"}}}}, "terms": "Musti"}}]
+            }],
+        }))
+        .unwrap();
+
+        let page: Page<Sourced<String>> = Page::from_value(value).unwrap();
+
+        assert_eq!("Musti", page.data[0].value);
+        assert!(page.data[0].sources[0].is_set());
+    }
+
+    #[test]
+    fn test_invalid_page() {
+        let page: crate::Result<Page> = Page::from_value(Value::from("Musti"));
+        assert!(page.is_err());
+    }
+
+    #[test]
+    fn test_cursor_in_paginate() {
+        let value: Value = serde_json::from_value(json!({
+            "data": [],
+            "after": [{"@ts": "1970-01-01T00:01:00Z"}, {"ts": 60, "action": "add"}],
+        }))
+        .unwrap();
+
+        let page: Page = Page::from_value(value).unwrap();
+
+        let mut paginate = Paginate::new(Classes::all());
+        paginate.after(page.after.unwrap());
+
+        let query = Query::from(paginate);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "paginate": { "classes": null },
+            "after": [
+                { "@ts": Utc.timestamp(60, 0) },
+                { "object": { "ts": 60, "action": "add" } }
+            ],
+            "size": 64,
+            "sources": false,
+            "events": false,
+        });
+
+        assert_eq!(expected, serialized);
+    }
+}
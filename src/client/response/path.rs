@@ -0,0 +1,392 @@
+use super::{SimpleValue, Value};
+use crate::error::Error;
+use std::{collections::BTreeMap, fmt};
+
+/// A step in a path into a nested `Value`, an object key or an array index.
+///
+/// Paths follow the semantics of the Fauna
+/// [Select](https://docs.fauna.com/fauna/current/reference/queryapi/read/select)
+/// function: a key only matches an object field and an index only matches an
+/// array element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+impl<'a> From<&'a str> for PathSegment {
+    fn from(key: &'a str) -> Self {
+        PathSegment::Key(key.to_string())
+    }
+}
+
+impl From<String> for PathSegment {
+    fn from(key: String) -> Self {
+        PathSegment::Key(key)
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> Self {
+        PathSegment::Index(index)
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathSegment::Key(key) => write!(f, "{}", key),
+            PathSegment::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+impl PathSegment {
+    fn get<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        match (self, value) {
+            (PathSegment::Key(key), Value::Simple(SimpleValue::Object(map))) => map.get(key),
+            (PathSegment::Index(i), Value::Simple(SimpleValue::Array(vec))) => vec.get(*i),
+            _ => None,
+        }
+    }
+
+    fn get_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+        match (self, value) {
+            (PathSegment::Key(key), Value::Simple(SimpleValue::Object(map))) => map.get_mut(key),
+            (PathSegment::Index(i), Value::Simple(SimpleValue::Array(vec))) => vec.get_mut(*i),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a JSON pointer into unescaped tokens. Returns `None` if the pointer
+/// is not empty and does not start with a slash.
+fn pointer_tokens(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+
+    if !pointer.starts_with('/') {
+        return None;
+    }
+
+    Some(
+        pointer[1..]
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect(),
+    )
+}
+
+/// Resolves a pointer token against the value, as an index for arrays and as a
+/// key for everything else.
+fn pointer_segment(token: String, value: &Value) -> Option<PathSegment> {
+    match value {
+        Value::Simple(SimpleValue::Array(_)) => {
+            if token.starts_with('+') || (token.starts_with('0') && token.len() > 1) {
+                None
+            } else {
+                token.parse().ok().map(PathSegment::Index)
+            }
+        }
+        _ => Some(PathSegment::Key(token)),
+    }
+}
+
+impl Value {
+    /// Looks up a value with a
+    /// [JSON pointer](https://tools.ietf.org/html/rfc6901), such as
+    /// `/data/owners/0/name`.
+    ///
+    /// Returns `None` if the pointer is invalid or the value does not exist.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// # use serde_json::json;
+    /// #
+    /// let value: Value = serde_json::from_value(json!({
+    ///     "data": { "owners": [{ "name": "Julius" }] }
+    /// })).unwrap();
+    ///
+    /// assert_eq!(Some("Julius"), value.pointer("/data/owners/0/name").and_then(Value::as_str));
+    /// assert_eq!(None, value.pointer("/data/owners/1/name"));
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        pointer_tokens(pointer)?
+            .into_iter()
+            .try_fold(self, |value, token| {
+                pointer_segment(token, value)?.get(value)
+            })
+    }
+
+    /// Mutably looks up a value with a JSON pointer, such as
+    /// `/data/owners/0/name`.
+    ///
+    /// Returns `None` if the pointer is invalid or the value does not exist.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        pointer_tokens(pointer)?
+            .into_iter()
+            .try_fold(self, |value, token| {
+                pointer_segment(token, value)?.get_mut(value)
+            })
+    }
+
+    /// Looks up a value from a path of object keys and array indices, like the
+    /// Fauna `Select` function.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// # use serde_json::json;
+    /// #
+    /// let value: Value = serde_json::from_value(json!({
+    ///     "data": { "owners": [{ "name": "Julius" }] }
+    /// })).unwrap();
+    ///
+    /// let path = [
+    ///     PathSegment::from("data"),
+    ///     PathSegment::from("owners"),
+    ///     PathSegment::from(0),
+    ///     PathSegment::from("name"),
+    /// ];
+    ///
+    /// assert_eq!(Some("Julius"), value.select(&path).and_then(Value::as_str));
+    /// ```
+    pub fn select(&self, path: &[PathSegment]) -> Option<&Value> {
+        path.iter()
+            .try_fold(self, |value, segment| segment.get(value))
+    }
+
+    /// Mutably looks up a value from a path of object keys and array
+    /// indices.
+    pub fn select_mut(&mut self, path: &[PathSegment]) -> Option<&mut Value> {
+        path.iter()
+            .try_fold(self, |value, segment| segment.get_mut(value))
+    }
+
+    /// Writes a value to the path, returning the previous value if there was
+    /// one.
+    ///
+    /// Missing objects on the way, and `null` values in their place, are
+    /// created. An index can point to an existing element or one past the
+    /// end of the array, appending the value. Other paths that do not fit the
+    /// structure are errors, keeping the objects created before the failing
+    /// segment.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// #
+    /// let mut value = Value::null();
+    ///
+    /// let path = [PathSegment::from("data"), PathSegment::from("name")];
+    /// value.set_path(&path, Value::from("Musti")).unwrap();
+    ///
+    /// assert_eq!(Some("Musti"), value["data"]["name"].as_str());
+    /// ```
+    pub fn set_path(
+        &mut self,
+        path: &[PathSegment],
+        new_value: Value,
+    ) -> crate::Result<Option<Value>> {
+        let (last, parents) = match path.split_last() {
+            Some(split) => split,
+            None => return Ok(Some(std::mem::replace(self, new_value))),
+        };
+
+        let mut current = self;
+
+        for segment in parents {
+            current = match segment {
+                PathSegment::Key(key) => {
+                    if current.is_null() {
+                        *current = Value::Simple(SimpleValue::Object(BTreeMap::new()));
+                    }
+
+                    match current {
+                        Value::Simple(SimpleValue::Object(map)) => map
+                            .entry(key.clone())
+                            .or_insert_with(|| Value::Simple(SimpleValue::Object(BTreeMap::new()))),
+                        _ => return Err(Error::ConversionError("Path key into a non-object")),
+                    }
+                }
+                PathSegment::Index(i) => match current {
+                    Value::Simple(SimpleValue::Array(vec)) => vec
+                        .get_mut(*i)
+                        .ok_or(Error::ConversionError("Path index out of bounds"))?,
+                    _ => return Err(Error::ConversionError("Path index into a non-array")),
+                },
+            };
+        }
+
+        match last {
+            PathSegment::Key(key) => {
+                if current.is_null() {
+                    *current = Value::Simple(SimpleValue::Object(BTreeMap::new()));
+                }
+
+                match current {
+                    Value::Simple(SimpleValue::Object(map)) => {
+                        Ok(map.insert(key.clone(), new_value))
+                    }
+                    _ => Err(Error::ConversionError("Path key into a non-object")),
+                }
+            }
+            PathSegment::Index(i) => match current {
+                Value::Simple(SimpleValue::Array(vec)) if *i < vec.len() => {
+                    Ok(Some(std::mem::replace(&mut vec[*i], new_value)))
+                }
+                Value::Simple(SimpleValue::Array(vec)) if *i == vec.len() => {
+                    vec.push(new_value);
+                    Ok(None)
+                }
+                Value::Simple(SimpleValue::Array(_)) => {
+                    Err(Error::ConversionError("Path index out of bounds"))
+                }
+                _ => Err(Error::ConversionError("Path index into a non-array")),
+            },
+        }
+    }
+
+    /// Removes the value in the path, returning it. Array elements after the
+    /// removed one are shifted to the left.
+    ///
+    /// Returns `None` if the value does not exist.
+    pub fn remove_path(&mut self, path: &[PathSegment]) -> Option<Value> {
+        let (last, parents) = path.split_last()?;
+
+        match (last, self.select_mut(parents)?) {
+            (PathSegment::Key(key), Value::Simple(SimpleValue::Object(map))) => map.remove(key),
+            (PathSegment::Index(i), Value::Simple(SimpleValue::Array(vec))) if *i < vec.len() => {
+                Some(vec.remove(*i))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    fn document() -> Value {
+        serde_json::from_value(json!({
+            "data": {
+                "name": "Musti",
+                "owners": [{ "name": "Julius" }, { "name": "Naukio" }],
+                "a/b": { "~c": 1 }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn path(segments: &[PathSegment]) -> Vec<PathSegment> {
+        segments.to_vec()
+    }
+
+    #[test]
+    fn test_pointer() {
+        let value = document();
+
+        assert_eq!(Some(&value), value.pointer(""));
+        assert_eq!(
+            Some("Musti"),
+            value.pointer("/data/name").and_then(Value::as_str)
+        );
+        assert_eq!(
+            Some("Naukio"),
+            value.pointer("/data/owners/1/name").and_then(Value::as_str)
+        );
+        assert_eq!(
+            Some(1),
+            value.pointer("/data/a~1b/~0c").and_then(Value::as_u64)
+        );
+
+        assert_eq!(None, value.pointer("data/name"));
+        assert_eq!(None, value.pointer("/data/owners/01/name"));
+        assert_eq!(None, value.pointer("/data/owners/2"));
+        assert_eq!(None, value.pointer("/data/name/0"));
+    }
+
+    #[test]
+    fn test_pointer_mut() {
+        let mut value = document();
+        *value.pointer_mut("/data/owners/0/name").unwrap() = Value::from("Pimeys");
+
+        assert_eq!(Some("Pimeys"), value["data"]["owners"][0]["name"].as_str());
+    }
+
+    #[test]
+    fn test_select() {
+        let value = document();
+
+        let owner = path(&["data".into(), "owners".into(), 0.into(), "name".into()]);
+        assert_eq!(Some("Julius"), value.select(&owner).and_then(Value::as_str));
+
+        let mismatch = path(&["data".into(), 0.into()]);
+        assert_eq!(None, value.select(&mismatch));
+
+        let string_index = path(&["data".into(), "owners".into(), "0".into()]);
+        assert_eq!(None, value.select(&string_index));
+    }
+
+    #[test]
+    fn test_select_mut() {
+        let mut value = document();
+
+        let name = path(&["data".into(), "name".into()]);
+        *value.select_mut(&name).unwrap() = Value::from("Naukio");
+
+        assert_eq!(Some("Naukio"), value["data"]["name"].as_str());
+    }
+
+    #[test]
+    fn test_set_path() {
+        let mut value = document();
+
+        let name = path(&["data".into(), "name".into()]);
+        let previous = value.set_path(&name, Value::from("Naukio")).unwrap();
+
+        assert_eq!(Some(Value::from("Musti")), previous);
+        assert_eq!(Some("Naukio"), value["data"]["name"].as_str());
+
+        let deep = path(&["data".into(), "home".into(), "city".into()]);
+        assert_eq!(None, value.set_path(&deep, Value::from("Berlin")).unwrap());
+        assert_eq!(Some("Berlin"), value["data"]["home"]["city"].as_str());
+
+        let push = path(&["data".into(), "owners".into(), 2.into()]);
+        value.set_path(&push, Value::from("Pimeys")).unwrap();
+        assert_eq!(Some("Pimeys"), value["data"]["owners"][2].as_str());
+    }
+
+    #[test]
+    fn test_set_path_errors() {
+        let mut value = document();
+
+        let out_of_bounds = path(&["data".into(), "owners".into(), 5.into()]);
+        assert!(value.set_path(&out_of_bounds, Value::null()).is_err());
+
+        let key_into_string = path(&["data".into(), "name".into(), "first".into()]);
+        assert!(value.set_path(&key_into_string, Value::null()).is_err());
+
+        let index_into_object = path(&["data".into(), 0.into()]);
+        assert!(value.set_path(&index_into_object, Value::null()).is_err());
+
+        assert_eq!(document(), value);
+    }
+
+    #[test]
+    fn test_remove_path() {
+        let mut value = document();
+
+        let first_owner = path(&["data".into(), "owners".into(), 0.into()]);
+        let removed = value.remove_path(&first_owner).unwrap();
+
+        assert_eq!(Some("Julius"), removed["name"].as_str());
+        assert_eq!(Some("Naukio"), value["data"]["owners"][0]["name"].as_str());
+
+        let name = path(&["data".into(), "name".into()]);
+        assert_eq!(Some(Value::from("Musti")), value.remove_path(&name));
+        assert_eq!(None, value.remove_path(&name));
+        assert_eq!(None, value.remove_path(&[]));
+    }
+}
@@ -0,0 +1,289 @@
+use super::{AnnotatedValue, SimpleValue, Value};
+use crate::{
+    expr::{Expr, Ref},
+    fql::{self, Node},
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::BTreeMap, fmt};
+
+/// A set identifier returned from Fauna with the `@set` annotation, e.g. the
+/// result of a `Match` or a `Union` query.
+///
+/// A set can be converted back to an `Expr` for paginating it again, and is
+/// displayed in FQL.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// # use serde_json::json;
+/// #
+/// let value: Value = serde_json::from_value(json!({
+///     "@set": {
+///         "match": { "@ref": { "class": { "@ref": { "id": "indexes" } }, "id": "cats_by_age" } },
+///         "terms": 7
+///     }
+/// })).unwrap();
+///
+/// let set = value.as_set().unwrap();
+/// assert_eq!("Match(Index(\"cats_by_age\"), 7)", set.to_string());
+///
+/// let query = Paginate::new(set.clone());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SetRef {
+    /// Instances matching the terms in an index.
+    Match {
+        index: Ref<'static>,
+        terms: Option<Value>,
+    },
+    /// Items in any of the sets.
+    Union(Vec<SetRef>),
+    /// Items in all of the sets.
+    Intersection(Vec<SetRef>),
+    /// Items in the first set not in the others.
+    Difference(Vec<SetRef>),
+    /// The set without duplicates.
+    Distinct(Box<SetRef>),
+    /// The items of the source used as terms in the target, an index ref or a
+    /// lambda in the query format.
+    Join { source: Box<SetRef>, target: Value },
+    /// Any other set, in the query format.
+    Other(Value),
+}
+
+impl SetRef {
+    /// Reads the set from the contents of the `@set` annotation. Sets of an
+    /// unknown shape are kept as `Other`.
+    pub fn from_value(value: Value) -> Self {
+        let obj = match value {
+            Value::Simple(SimpleValue::Object(obj)) => obj,
+            value => return SetRef::Other(value),
+        };
+
+        let keys: Vec<&str> = obj.keys().map(String::as_str).collect();
+
+        let parsed = match keys.as_slice() {
+            ["match"] | ["match", "terms"] => match obj.get("match") {
+                Some(Value::Annotated(AnnotatedValue::Ref(index))) => Some(SetRef::Match {
+                    index: index.clone(),
+                    terms: obj.get("terms").cloned(),
+                }),
+                _ => None,
+            },
+            ["union"] => Self::nested_sets(&obj["union"]).map(SetRef::Union),
+            ["intersection"] => Self::nested_sets(&obj["intersection"]).map(SetRef::Intersection),
+            ["difference"] => Self::nested_sets(&obj["difference"]).map(SetRef::Difference),
+            ["distinct"] => Self::nested_set(&obj["distinct"]).map(SetRef::Distinct),
+            ["join", "with"] => Self::nested_set(&obj["join"]).map(|source| SetRef::Join {
+                source,
+                target: obj["with"].clone(),
+            }),
+            _ => None,
+        };
+
+        match parsed {
+            Some(set) => set,
+            None => SetRef::Other(Value::Simple(SimpleValue::Object(obj))),
+        }
+    }
+
+    fn nested_set(value: &Value) -> Option<Box<SetRef>> {
+        match value {
+            Value::Annotated(AnnotatedValue::Set(set)) => Some(set.clone()),
+            _ => None,
+        }
+    }
+
+    fn nested_sets(value: &Value) -> Option<Vec<SetRef>> {
+        match value {
+            Value::Simple(SimpleValue::Array(values)) => values
+                .iter()
+                .map(|value| Self::nested_set(value).map(|set| *set))
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /// The contents of the `@set` annotation, as Fauna returned them.
+    pub fn to_value(&self) -> Value {
+        fn object(pairs: Vec<(&str, Value)>) -> Value {
+            let obj: BTreeMap<String, Value> =
+                pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+
+            Value::Simple(SimpleValue::Object(obj))
+        }
+
+        fn sets(sets: &[SetRef]) -> Value {
+            Value::Simple(SimpleValue::Array(sets.iter().map(annotated).collect()))
+        }
+
+        fn annotated(set: &SetRef) -> Value {
+            Value::Annotated(AnnotatedValue::Set(Box::new(set.clone())))
+        }
+
+        match self {
+            SetRef::Match { index, terms } => {
+                let mut pairs = vec![("match", Value::from(index.clone()))];
+
+                if let Some(terms) = terms {
+                    pairs.push(("terms", terms.clone()));
+                }
+
+                object(pairs)
+            }
+            SetRef::Union(union) => object(vec![("union", sets(union))]),
+            SetRef::Intersection(intersection) => {
+                object(vec![("intersection", sets(intersection))])
+            }
+            SetRef::Difference(difference) => object(vec![("difference", sets(difference))]),
+            SetRef::Distinct(set) => object(vec![("distinct", annotated(set))]),
+            SetRef::Join { source, target } => {
+                object(vec![("join", annotated(source)), ("with", target.clone())])
+            }
+            SetRef::Other(value) => value.clone(),
+        }
+    }
+}
+
+impl Serialize for SetRef {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_value().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SetRef {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Value::deserialize(deserializer).map(SetRef::from_value)
+    }
+}
+
+impl From<SetRef> for Expr<'static> {
+    fn from(set: SetRef) -> Self {
+        Expr::from(Value::Annotated(AnnotatedValue::Set(Box::new(set))))
+    }
+}
+
+impl fmt::Display for SetRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fql::write_node(&Node::set(self), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    fn index(id: &str) -> serde_json::Value {
+        json!({ "@ref": { "class": { "@ref": { "id": "indexes" } }, "id": id } })
+    }
+
+    fn set(json: serde_json::Value) -> SetRef {
+        let value: Value = serde_json::from_value(json!({ "@set": json })).unwrap();
+        value.as_set().unwrap().clone()
+    }
+
+    #[test]
+    fn test_match() {
+        let matched = set(json!({ "match": index("cats_by_age"), "terms": 7 }));
+
+        assert_eq!(
+            SetRef::Match {
+                index: Ref::index("cats_by_age"),
+                terms: Some(Value::from(7)),
+            },
+            matched
+        );
+
+        let all = set(json!({ "match": index("all_cats") }));
+
+        assert_eq!(
+            SetRef::Match {
+                index: Ref::index("all_cats"),
+                terms: None,
+            },
+            all
+        );
+    }
+
+    #[test]
+    fn test_combinations() {
+        let cats = json!({ "@set": { "match": index("all_cats") } });
+        let dogs = json!({ "@set": { "match": index("all_dogs") } });
+
+        let union = set(json!({ "union": [cats.clone(), dogs.clone()] }));
+        let distinct = set(json!({ "distinct": cats.clone() }));
+        let join = set(json!({ "join": cats.clone(), "with": index("owners_by_pet") }));
+
+        match union {
+            SetRef::Union(ref sets) => assert_eq!(2, sets.len()),
+            _ => panic!("expected a union, got {:?}", union),
+        }
+
+        match distinct {
+            SetRef::Distinct(_) => (),
+            _ => panic!("expected distinct, got {:?}", distinct),
+        }
+
+        match join {
+            SetRef::Join { ref target, .. } => {
+                assert_eq!(Some(&Ref::index("owners_by_pet")), target.as_reference())
+            }
+            _ => panic!("expected a join, got {:?}", join),
+        }
+
+        assert_eq!(
+            "Union(Match(Index(\"all_cats\")), Match(Index(\"all_dogs\")))",
+            union.to_string()
+        );
+        assert_eq!("Distinct(Match(Index(\"all_cats\")))", distinct.to_string());
+        assert_eq!(
+            "Join(Match(Index(\"all_cats\")), Index(\"owners_by_pet\"))",
+            join.to_string()
+        );
+    }
+
+    #[test]
+    fn test_other() {
+        let events = set(json!({ "events": { "@ref": { "id": "1" } } }));
+
+        assert_eq!(
+            SetRef::Other(
+                serde_json::from_value(json!({ "events": { "@ref": { "id": "1" } } })).unwrap()
+            ),
+            events
+        );
+    }
+
+    #[test]
+    fn test_round_trip_to_expr() {
+        let json = json!({
+            "@set": {
+                "difference": [
+                    { "@set": { "match": index("cats_by_age"), "terms": [7, "old"] } },
+                    { "@set": { "join": { "@set": { "match": index("all_dogs") } }, "with": { "lambda": "x", "expr": { "var": "x" } } } },
+                    { "@set": { "events": { "@ref": { "id": "1" } } } }
+                ]
+            }
+        });
+
+        let value: Value = serde_json::from_value(json.clone()).unwrap();
+        let set = value.as_set().unwrap().clone();
+
+        assert_eq!(json, serde_json::to_value(&value).unwrap());
+        assert_eq!(
+            json,
+            serde_json::to_value(&Expr::from(set.clone())).unwrap()
+        );
+
+        let query = Query::from(Paginate::new(set));
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(json, serialized["paginate"]);
+    }
+}
@@ -1,16 +1,23 @@
-use super::ValueIndex;
+use super::{SetRef, ValueIndex};
 use crate::{
     expr::{Bytes, Number, Ref},
     serde::base64_bytes,
 };
 use chrono::{DateTime, NaiveDate, Utc};
-use std::collections::BTreeMap;
+use std::{cmp::Ordering, collections::BTreeMap};
 
 /// Represents any value returned from Fauna.
 ///
+/// Values are ordered like Fauna orders them in indexes: numbers first, then
+/// strings, refs, timestamps, dates and booleans. Byte arrays, arrays,
+/// objects, sets and queries come after these, in that order, and null is
+/// always last. Values of the same type are compared by their contents, arrays
+/// and objects element by element. See [Number](../expr/enum.Number.html) for
+/// how numbers and `NaN` compare.
+///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/types)
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum Value {
     /// A value with an annotation for its type definition.
@@ -19,7 +26,7 @@ pub enum Value {
     Simple(SimpleValue),
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum SimpleValue {
     /// String data types store any letters, numbers, whitespaces, and/or symbols in a fixed order.
@@ -42,7 +49,7 @@ pub enum SimpleValue {
     Null,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Hash)]
 pub enum AnnotatedValue {
     /// Denotes a resource ref. Refs may be extracted from instances, or
     /// constructed using the ref function.
@@ -60,13 +67,61 @@ pub enum AnnotatedValue {
     Date(NaiveDate),
     /// Denotes a set identifier.
     #[serde(rename = "@set")]
-    Set(Box<Value>),
+    Set(Box<SetRef>),
     /// Stores an instant in time expressed as a calendar date and time of day
     /// in UTC.
     #[serde(rename = "@ts")]
     Timestamp(DateTime<Utc>),
 }
 
+impl Value {
+    /// The position of the type in the Fauna ordering.
+    fn type_rank(&self) -> u8 {
+        match self {
+            Value::Simple(SimpleValue::Number(_)) => 0,
+            Value::Simple(SimpleValue::String(_)) => 1,
+            Value::Annotated(AnnotatedValue::Ref(_)) => 2,
+            Value::Annotated(AnnotatedValue::Timestamp(_)) => 3,
+            Value::Annotated(AnnotatedValue::Date(_)) => 4,
+            Value::Simple(SimpleValue::Boolean(_)) => 5,
+            Value::Annotated(AnnotatedValue::Bytes(_)) => 6,
+            Value::Simple(SimpleValue::Array(_)) => 7,
+            Value::Simple(SimpleValue::Object(_)) => 8,
+            Value::Annotated(AnnotatedValue::Set(_)) => 9,
+            Value::Annotated(AnnotatedValue::Query(_)) => 10,
+            Value::Simple(SimpleValue::Null) => 11,
+        }
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use AnnotatedValue as A;
+        use SimpleValue as S;
+
+        match (self, other) {
+            (Value::Simple(S::Number(a)), Value::Simple(S::Number(b))) => a.cmp(b),
+            (Value::Simple(S::String(a)), Value::Simple(S::String(b))) => a.cmp(b),
+            (Value::Annotated(A::Ref(a)), Value::Annotated(A::Ref(b))) => a.cmp(b),
+            (Value::Annotated(A::Timestamp(a)), Value::Annotated(A::Timestamp(b))) => a.cmp(b),
+            (Value::Annotated(A::Date(a)), Value::Annotated(A::Date(b))) => a.cmp(b),
+            (Value::Simple(S::Boolean(a)), Value::Simple(S::Boolean(b))) => a.cmp(b),
+            (Value::Annotated(A::Bytes(a)), Value::Annotated(A::Bytes(b))) => a.cmp(b),
+            (Value::Simple(S::Array(a)), Value::Simple(S::Array(b))) => a.cmp(b),
+            (Value::Simple(S::Object(a)), Value::Simple(S::Object(b))) => a.cmp(b),
+            (Value::Annotated(A::Set(a)), Value::Annotated(A::Set(b))) => a.cmp(b),
+            (Value::Annotated(A::Query(a)), Value::Annotated(A::Query(b))) => a.cmp(b),
+            _ => self.type_rank().cmp(&other.type_rank()),
+        }
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Default for Value {
     fn default() -> Self {
         Value::null()
@@ -449,8 +504,8 @@ impl Value {
         }
     }
 
-    /// Returns a `Set` for `Set` values, otherwise `None`.
-    pub fn as_set(&self) -> Option<&Value> {
+    /// Returns a `SetRef` for `Set` values, otherwise `None`.
+    pub fn as_set(&self) -> Option<&SetRef> {
         match self {
             Value::Annotated(AnnotatedValue::Set(set)) => Some(&*set),
             _ => None,
@@ -473,3 +528,93 @@ impl Value {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use chrono::{offset::TimeZone, NaiveDate, Utc};
+    use serde_json::{self, json};
+    use std::{
+        collections::{BTreeMap, HashSet},
+        f64,
+    };
+
+    fn value(json: serde_json::Value) -> Value {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_cross_type_order() {
+        let mut values = vec![
+            Value::null(),
+            value(json!(true)),
+            Value::from(NaiveDate::from_ymd_opt(2011, 7, 7).unwrap()),
+            Value::from(Utc.timestamp_opt(60, 0).unwrap()),
+            Value::from(Ref::instance("musti")),
+            Value::from("musti"),
+            Value::from(1),
+        ];
+
+        let expected: Vec<Value> = values.iter().cloned().rev().collect();
+        values.sort();
+
+        assert_eq!(expected, values);
+    }
+
+    #[test]
+    fn test_numbers_order_numerically() {
+        let mut values = vec![
+            Value::from(f64::NAN),
+            Value::from(2.5),
+            Value::from(-3),
+            Value::from(2u64),
+            Value::from(f64::NEG_INFINITY),
+        ];
+
+        values.sort();
+
+        assert_eq!(Value::from(f64::NEG_INFINITY), values[0]);
+        assert_eq!(Value::from(-3), values[1]);
+        assert_eq!(Value::from(2u64), values[2]);
+        assert_eq!(Value::from(2.5), values[3]);
+        assert!(values[4].as_f64().unwrap().is_nan());
+
+        assert_eq!(Value::from(1), Value::from(1.0));
+        assert_eq!(Value::from(1i64), value(json!(1)));
+    }
+
+    #[test]
+    fn test_arrays_and_objects_elementwise() {
+        assert!(value(json!([1, 2])) < value(json!([1, 3])));
+        assert!(value(json!([1, 2])) < value(json!([1, 2, 0])));
+        assert!(value(json!(["a"])) < value(json!([null])));
+
+        assert!(value(json!({ "a": 1 })) < value(json!({ "a": 2 })));
+        assert!(value(json!({ "a": 2 })) < value(json!({ "b": 1 })));
+
+        assert!(value(json!([1])) < value(json!({ "a": 1 })));
+        assert!(value(json!({ "a": 1 })) < Value::null());
+    }
+
+    #[test]
+    fn test_in_collections() {
+        let mut set = HashSet::new();
+        set.insert(Value::from(1));
+        set.insert(Value::from(1.0));
+        set.insert(value(json!({ "name": "Musti" })));
+        set.insert(value(json!({ "name": "Musti" })));
+
+        assert_eq!(2, set.len());
+
+        let mut map = BTreeMap::new();
+        map.insert(Value::from("b"), 2);
+        map.insert(Value::from("a"), 1);
+        map.insert(Value::from(0), 0);
+
+        let keys: Vec<Value> = map.keys().cloned().collect();
+        assert_eq!(
+            vec![Value::from(0), Value::from("a"), Value::from("b")],
+            keys
+        );
+    }
+}
@@ -1,5 +1,7 @@
-use super::{Client, Response};
+use super::{Client, ItemStream, Page, PageStream, Response, Value};
 use crate::expr::Expr;
+use futures::Stream;
+use serde::de::DeserializeOwned;
 use std::sync::Mutex;
 use tokio::runtime::Runtime;
 
@@ -17,6 +19,11 @@ impl SyncClient {
         })
     }
 
+    /// Replace the secret used for authenticating the requests.
+    pub fn set_secret<'a>(&self, secret: impl Into<std::borrow::Cow<'a, str>>) {
+        self.inner.set_secret(secret)
+    }
+
     pub fn query<'a, Q>(&self, query: Q) -> crate::Result<Response>
     where
         Q: Into<Expr<'a>>,
@@ -26,4 +33,127 @@ impl SyncClient {
             .unwrap()
             .block_on(self.inner.query(query))
     }
+
+    /// Send a query to Fauna servers and read the resulting resource into the
+    /// given type.
+    pub fn query_as<'a, T, Q>(&self, query: Q) -> crate::Result<T>
+    where
+        Q: Into<Expr<'a>>,
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.runtime
+            .lock()
+            .unwrap()
+            .block_on(self.inner.query_as(query))
+    }
+
+    /// Paginate through all the items in the given set, `size` items per
+    /// page. Returns an iterator of pages, fetching a new page from Fauna when
+    /// the previous page is consumed.
+    pub fn paginate<'a, S>(&self, set: S, size: u32) -> SyncPages<'_>
+    where
+        S: Into<Expr<'a>>,
+    {
+        SyncPages {
+            client: self,
+            stream: Some(self.inner.paginate(set, size)),
+        }
+    }
+
+    fn next_from<S>(&self, stream: S) -> Option<(crate::Result<S::Item>, S)>
+    where
+        S: Stream<Error = crate::error::Error> + Send + 'static,
+        S::Item: Send + 'static,
+    {
+        let next = self.runtime.lock().unwrap().block_on(stream.into_future());
+
+        match next {
+            Ok((Some(item), stream)) => Some((Ok(item), stream)),
+            Ok((None, _)) => None,
+            Err((error, stream)) => Some((Err(error), stream)),
+        }
+    }
+}
+
+/// A blocking iterator of pages. Should be created with
+/// [SyncClient::paginate](struct.SyncClient.html#method.paginate).
+pub struct SyncPages<'a> {
+    client: &'a SyncClient,
+    stream: Option<PageStream>,
+}
+
+impl<'a> SyncPages<'a> {
+    /// Traverse the set from the end to the beginning, following the `before`
+    /// cursors.
+    pub fn reverse(&mut self) -> &mut Self {
+        if let Some(ref mut stream) = self.stream {
+            stream.reverse();
+        }
+
+        self
+    }
+
+    /// Read all the pages from the set at the given point in time.
+    pub fn timestamp<'b>(&mut self, timestamp: impl Into<Expr<'b>>) -> &mut Self {
+        if let Some(ref mut stream) = self.stream {
+            stream.timestamp(timestamp);
+        }
+
+        self
+    }
+
+    /// A `Lambda` applied to every item of every page in Fauna.
+    pub fn mapper<'b>(&mut self, lambda: impl Into<Expr<'b>>) -> &mut Self {
+        if let Some(ref mut stream) = self.stream {
+            stream.mapper(lambda);
+        }
+
+        self
+    }
+
+    /// Transforms the iterator of pages into an iterator of the items in the
+    /// pages.
+    pub fn items(self) -> SyncItems<'a> {
+        SyncItems {
+            client: self.client,
+            stream: self.stream.map(PageStream::items),
+        }
+    }
+}
+
+impl<'a> Iterator for SyncPages<'a> {
+    type Item = crate::Result<Page>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let stream = self.stream.take()?;
+        let (page, stream) = self.client.next_from(stream)?;
+
+        if page.is_ok() {
+            self.stream = Some(stream);
+        }
+
+        Some(page)
+    }
+}
+
+/// A blocking iterator of the items in all the pages of a set. Created with
+/// [SyncPages::items](struct.SyncPages.html#method.items).
+pub struct SyncItems<'a> {
+    client: &'a SyncClient,
+    stream: Option<ItemStream>,
+}
+
+impl<'a> Iterator for SyncItems<'a> {
+    type Item = crate::Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let stream = self.stream.take()?;
+        let (item, stream) = self.client.next_from(stream)?;
+
+        if item.is_ok() {
+            self.stream = Some(stream);
+        }
+
+        Some(item)
+    }
 }
@@ -0,0 +1,138 @@
+//! Mapping Rust structs to Fauna classes.
+//!
+//! The `FaunaDocument` trait is usually implemented with the derive macro of
+//! the same name, available with the `derive` feature:
+//!
+//! ```ignore
+//! use faunadb::{prelude::*, FaunaDocument};
+//!
+//! #[derive(FaunaDocument)]
+//! #[fauna(class = "users")]
+//! struct User {
+//!     #[fauna(reference)]
+//!     id: Option<Ref<'static>>,
+//!     #[fauna(rename = "user_name")]
+//!     name: String,
+//!     #[fauna(credentials)]
+//!     password: String,
+//!     #[fauna(nested)]
+//!     address: Address,
+//!     #[fauna(skip)]
+//!     cached: Option<String>,
+//! }
+//! ```
+//!
+//! Fields are written with [to_expr](../fn.to_expr.html) and read with
+//! [from_value](../fn.from_value.html). The field attributes are:
+//!
+//! - `rename = "name"` uses a different key in the instance data.
+//! - `skip` never writes the field, reading it as `Default::default()`.
+//! - `credentials` writes the field to the instance credentials instead of the
+//!   data. Credentials are never returned from Fauna, so the field is read as
+//!   `Default::default()`.
+//! - `nested` writes and reads the field through its own `FaunaDocument`
+//!   implementation.
+//! - `reference` and `ts` are filled from the ref and the timestamp of a `Get`
+//!   response and never written.
+use crate::{
+    client::Value,
+    expr::{Expr, Object, Ref},
+    query::write::{Create, Replace, Update, UpdateParams},
+};
+
+/// A Rust type stored as instances of a Fauna class.
+pub trait FaunaDocument: Sized {
+    /// The name of the class.
+    const CLASS: &'static str;
+
+    /// The instance data, without credentials.
+    fn to_data(&self) -> crate::Result<Object<'static>>;
+
+    /// The instance credentials, if any of the fields are marked as
+    /// credentials.
+    fn to_credentials(&self) -> crate::Result<Option<Object<'static>>> {
+        Ok(None)
+    }
+
+    /// Reads the type from the `data` object of an instance.
+    fn from_data(data: &Value) -> crate::Result<Self>;
+
+    /// Reads the type from a full instance, such as a response to `Get`,
+    /// including the ref and timestamp.
+    fn from_resource(resource: &Value) -> crate::Result<Self> {
+        Self::from_data(resource.get("data").unwrap_or(&Value::default()))
+    }
+
+    /// A reference to the class.
+    fn class_ref() -> Ref<'static> {
+        Ref::class(Self::CLASS)
+    }
+
+    /// A `Create` query for a new instance of the class.
+    fn create(&self) -> crate::Result<Create<'static>> {
+        let mut create = Create::new(Self::class_ref(), self.to_data()?);
+
+        if let Some(credentials) = self.to_credentials()? {
+            create.credentials(credentials);
+        }
+
+        Ok(create)
+    }
+
+    /// A `Replace` query substituting the instance behind `reference`.
+    fn replace(&self, reference: impl Into<Expr<'static>>) -> crate::Result<Replace<'static>> {
+        let mut params = Object::default();
+        params.insert("data", self.to_data()?);
+
+        if let Some(credentials) = self.to_credentials()? {
+            params.insert("credentials", credentials);
+        }
+
+        Ok(Replace::new(reference, params))
+    }
+
+    /// An `Update` query writing all the fields to the instance behind
+    /// `reference`.
+    fn update(&self, reference: impl Into<Expr<'static>>) -> crate::Result<Update<'static>> {
+        let mut params = UpdateParams::new();
+        params.data(self.to_data()?);
+
+        if let Some(credentials) = self.to_credentials()? {
+            params.credentials(credentials);
+        }
+
+        Ok(Update::new(reference, params))
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use crate::{client::Value, error::Error};
+    use serde::de::DeserializeOwned;
+
+    pub use crate::expr::Object;
+
+    /// Reads one field of an object, a missing field being `null`.
+    pub fn read_field<T>(object: &Value, key: &str) -> crate::Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let value = object.get(key).cloned().unwrap_or_default();
+
+        crate::from_value(value).map_err(|e| match e {
+            Error::DecodeError(e) => Error::DecodeError(e.prepend(key.to_string())),
+            e => e,
+        })
+    }
+
+    /// Reads a nested document from one field of an object.
+    pub fn read_nested<T>(object: &Value, key: &str) -> crate::Result<T>
+    where
+        T: super::FaunaDocument,
+    {
+        T::from_data(object.get(key).unwrap_or(&Value::default())).map_err(|e| match e {
+            Error::DecodeError(e) => Error::DecodeError(e.prepend(key.to_string())),
+            e => e,
+        })
+    }
+}
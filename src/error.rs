@@ -1,5 +1,6 @@
 use crate::client::Value;
 use failure::{self, Fail};
+use std::fmt;
 
 #[derive(Debug, Fail)]
 pub enum Error {
@@ -27,6 +28,12 @@ pub enum Error {
     DatabaseError(String),
     #[fail(display = "Couldn't convert data: {}", _0)]
     ConversionError(&'static str),
+    #[fail(display = "Couldn't decode value: {}", _0)]
+    DecodeError(DecodeError),
+    #[fail(display = "Couldn't encode value: {}", _0)]
+    EncodeError(EncodeError),
+    #[fail(display = "Couldn't parse FQL: {}", _0)]
+    ParseError(ParseError),
     #[cfg(feature = "sync_client")]
     #[fail(display = "IO Error: {}", _0)]
     IoError(failure::Error),
@@ -49,6 +56,131 @@ pub struct FaunaError {
     pub description: String,
 }
 
+/// An error from reading a `Value` into a Rust type, pointing to the position
+/// where the reading failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodeError {
+    path: Vec<String>,
+    message: String,
+}
+
+impl DecodeError {
+    /// A [JSON pointer](https://tools.ietf.org/html/rfc6901) to the failing
+    /// value, e.g. `/data/owner/0/name`. Empty if the error is in the root
+    /// value.
+    pub fn path(&self) -> String {
+        self.path
+            .iter()
+            .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+            .collect()
+    }
+
+    /// The reason of the failure.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub(crate) fn prepend(mut self, segment: String) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{} at {}", self.message, self.path())
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl serde::de::Error for DecodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        DecodeError {
+            path: Vec::new(),
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// An error from writing a Rust type as an `Expr`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodeError {
+    message: String,
+}
+
+impl EncodeError {
+    /// The reason of the failure.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+impl serde::ser::Error for EncodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        EncodeError {
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// An error from parsing FQL text, pointing to the position of the failure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    message: String,
+    line: usize,
+    column: usize,
+}
+
+impl ParseError {
+    pub(crate) fn new(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self {
+            message: message.into(),
+            line,
+            column,
+        }
+    }
+
+    /// The reason of the failure.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The line of the failure, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the failure in characters, starting from 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 impl From<native_tls::Error> for Error {
     fn from(e: native_tls::Error) -> Self {
         Error::ConnectionError(e.into())
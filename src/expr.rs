@@ -1,21 +1,57 @@
 //! A Fauna expression that is either a value or a function that evaluates to a value.
 mod array;
+mod canonical;
 mod number;
 mod object;
+mod ops;
+mod owned;
 mod permission;
 mod reference;
 mod set;
+mod validate;
 
-use crate::{query::Query, serde::base64_bytes};
+use crate::{
+    client::{AnnotatedValue, SimpleValue, Value},
+    query::Query,
+    serde::base64_bytes,
+};
 use chrono::{DateTime, NaiveDate, Utc};
-use std::{borrow::Cow, fmt};
+use serde::{de, Deserialize, Deserializer};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+    mem,
+};
 
 pub use array::{Array, Bytes};
 pub use number::*;
 pub use object::Object;
+pub use owned::IntoOwned;
 pub use permission::*;
 pub use reference::Ref;
 pub use set::Set;
+pub use validate::{Finding, Problem, Validator, DEFAULT_MAX_PAYLOAD_SIZE, MAX_PAGE_SIZE};
+
+into_owned!(
+    enum AnnotatedExpr {
+        Quote,
+        Bytes,
+        Date,
+        Ref,
+        Set,
+        Timestamp,
+        Object,
+    }
+);
+
+into_owned!(
+    enum Expr {
+        Annotated,
+        Query,
+        Simple,
+    }
+);
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
@@ -49,7 +85,39 @@ pub enum SimpleExpr<'a> {
     Null,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Numbers are compared in their wire format, so `1` and `1.0` differ.
+impl<'a> PartialEq for SimpleExpr<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SimpleExpr::String(a), SimpleExpr::String(b)) => a == b,
+            (SimpleExpr::Number(a), SimpleExpr::Number(b)) => a.wire() == b.wire(),
+            (SimpleExpr::Boolean(a), SimpleExpr::Boolean(b)) => a == b,
+            (SimpleExpr::Array(a), SimpleExpr::Array(b)) => a == b,
+            (SimpleExpr::Object(a), SimpleExpr::Object(b)) => a == b,
+            (SimpleExpr::Null, SimpleExpr::Null) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Eq for SimpleExpr<'a> {}
+
+impl<'a> Hash for SimpleExpr<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        mem::discriminant(self).hash(state);
+
+        match self {
+            SimpleExpr::String(s) => s.hash(state),
+            SimpleExpr::Number(n) => n.wire().hash(state),
+            SimpleExpr::Boolean(b) => b.hash(state),
+            SimpleExpr::Array(a) => a.hash(state),
+            SimpleExpr::Object(o) => o.hash(state),
+            SimpleExpr::Null => (),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
 /// A special expression with an annotation marker.
 pub enum AnnotatedExpr<'a> {
     /// Quoted expression will not be evaluated in Fauna, good for storing
@@ -89,9 +157,17 @@ pub enum AnnotatedExpr<'a> {
 ///
 /// Expressions should be created using the `From`/`Into` traits.
 ///
+/// Expressions are compared and hashed structurally in their wire format:
+/// `Expr::from(1)` and `Expr::from(1.0)` are different, while `-0.0` equals
+/// `0.0`. Response values compare numbers by their value instead, as
+/// described in [Number](enum.Number.html). For a representation stable
+/// between processes, see
+/// [to_canonical_json](#method.to_canonical_json) and
+/// [fingerprint](#method.fingerprint).
+///
 /// See the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/types).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum Expr<'a> {
     Annotated(AnnotatedExpr<'a>),
@@ -105,47 +181,37 @@ impl<'a> std::default::Default for Expr<'a> {
     }
 }
 
-impl<'a> fmt::Display for Expr<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Expr::Simple(SimpleExpr::String(s)) => write!(f, "\"{}\"", s),
-            Expr::Simple(SimpleExpr::Number(Number::Double(d))) => write!(f, "{}", d),
-            Expr::Simple(SimpleExpr::Number(Number::Float(flt))) => write!(f, "{}", flt),
-            Expr::Simple(SimpleExpr::Number(Number::Int(i))) => write!(f, "{}", i),
-            Expr::Simple(SimpleExpr::Number(Number::UInt(i))) => write!(f, "{}", i),
-            Expr::Simple(SimpleExpr::Boolean(b)) => write!(f, "{}", b),
-            Expr::Simple(SimpleExpr::Null) => write!(f, "null"),
-            Expr::Simple(SimpleExpr::Array(v)) => {
-                let exprs: Vec<String> = v.0.iter().map(|e| format!("{}", e)).collect();
-                write!(f, "[{}]", exprs.join(","))
+impl<'a> Expr<'a> {
+    /// Converts the value without annotating the objects, for values already
+    /// in the query format, such as the contents of sets and quoted queries.
+    fn raw_value(value: Value) -> Expr<'static> {
+        match value {
+            Value::Simple(SimpleValue::Array(values)) => {
+                let exprs: Vec<Expr<'static>> = values.into_iter().map(Expr::raw_value).collect();
+                Expr::from(Array::from(exprs))
+            }
+            Value::Simple(SimpleValue::Object(obj)) => {
+                let obj: BTreeMap<Cow<'static, str>, Expr<'static>> = obj
+                    .into_iter()
+                    .map(|(k, v)| (Cow::from(k), Expr::raw_value(v)))
+                    .collect();
+
+                Expr::Simple(SimpleExpr::Object(Box::new(Object::from(obj))))
             }
-            Expr::Simple(SimpleExpr::Object(o)) => write!(f, "{}", o),
-            Expr::Annotated(AnnotatedExpr::Object(o)) => write!(f, "{}", o),
-            Expr::Annotated(AnnotatedExpr::Bytes(b)) => write!(f, "{}", base64::encode(&b.0)),
-            Expr::Annotated(AnnotatedExpr::Date(d)) => write!(f, "{}", d),
-            Expr::Annotated(AnnotatedExpr::Ref(r)) => write!(f, "{}", r),
-            Expr::Annotated(AnnotatedExpr::Set(s)) => write!(f, "{}", s),
-            Expr::Annotated(AnnotatedExpr::Timestamp(ts)) => write!(f, "{}", ts),
-            Expr::Annotated(AnnotatedExpr::Quote(q)) => write!(f, "Quote({:?})", q),
-            Expr::Query(query) => write!(f, "Query({:?})", query),
+            value => Expr::from(value),
         }
     }
-}
 
-impl<'a> Expr<'a> {
-    /// This hack is here for now for reusing the resulting `Expr` from FaunaDB.
-    /// Due to a deficiency the resulting object will lose its annotation, and
-    /// we must annotate it again for Fauna to accept the data.
-    pub(crate) fn reuse(self) -> Self {
+    /// Annotates the objects in an expression built from unannotated data,
+    /// for the deprecated `reuse` methods.
+    pub(crate) fn reannotate(self) -> Self {
         match self {
-            Expr::Simple(SimpleExpr::Object(o)) => {
-                Expr::Annotated(AnnotatedExpr::Object(Box::new(o.reuse())))
-            }
-            Expr::Annotated(AnnotatedExpr::Object(o)) => {
-                Expr::Annotated(AnnotatedExpr::Object(Box::new(o.reuse())))
+            Expr::Simple(SimpleExpr::Object(o)) | Expr::Annotated(AnnotatedExpr::Object(o)) => {
+                let obj = o.0.into_iter().map(|(k, v)| (k, v.reannotate())).collect();
+                Expr::from(Object(obj))
             }
-            Expr::Simple(SimpleExpr::Array(v)) => {
-                Expr::Simple(SimpleExpr::Array(Box::new(v.reuse())))
+            Expr::Simple(SimpleExpr::Array(a)) => {
+                Expr::from(Array(a.0.into_iter().map(Expr::reannotate).collect()))
             }
             expr => expr,
         }
@@ -242,6 +308,165 @@ impl<'a> From<Set<'a>> for Expr<'a> {
     }
 }
 
+/// Converts a value returned from Fauna back to an expression, e.g. for
+/// writing a previous response into another instance.
+///
+/// Objects are annotated again, and annotated values are kept as they are.
+/// Sets and quoted queries are written back exactly as Fauna returned them,
+/// so serializing the expression gives back the same value.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// # use serde_json::json;
+/// #
+/// let value: Value = serde_json::from_value(json!({
+///     "name": "Musti",
+///     "friend": { "@ref": { "id": "naukio" } },
+/// })).unwrap();
+///
+/// let query = Create::new(Class::find("cats"), Expr::from(value));
+/// ```
+impl From<Value> for Expr<'static> {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Simple(SimpleValue::String(s)) => Expr::from(s),
+            Value::Simple(SimpleValue::Number(n)) => Expr::from(n),
+            Value::Simple(SimpleValue::Boolean(b)) => Expr::from(b),
+            Value::Simple(SimpleValue::Null) => Expr::null(),
+            Value::Simple(SimpleValue::Array(values)) => {
+                let exprs: Vec<Expr<'static>> = values.into_iter().map(Expr::from).collect();
+                Expr::from(Array::from(exprs))
+            }
+            Value::Simple(SimpleValue::Object(obj)) => {
+                let obj: BTreeMap<Cow<'static, str>, Expr<'static>> = obj
+                    .into_iter()
+                    .map(|(k, v)| (Cow::from(k), Expr::from(v)))
+                    .collect();
+
+                Expr::from(Object::from(obj))
+            }
+            Value::Annotated(AnnotatedValue::Ref(r)) => Expr::from(r),
+            Value::Annotated(AnnotatedValue::Bytes(b)) => Expr::from(b),
+            Value::Annotated(AnnotatedValue::Date(d)) => Expr::from(d),
+            Value::Annotated(AnnotatedValue::Timestamp(ts)) => Expr::from(ts),
+            Value::Annotated(AnnotatedValue::Query(q)) => Expr::raw_value(*q).into_quoted(),
+            Value::Annotated(AnnotatedValue::Set(s)) => {
+                Expr::from(Set::raw(Expr::raw_value(s.to_value())))
+            }
+        }
+    }
+}
+
+/// Reads an expression in the wire format, e.g. a query from a JSON file or
+/// the body of a function returned in a `@query` value.
+///
+/// Objects with the keys of a function are read as a `Query`, and `object`
+/// keys as object literals. Other objects, and functions with arguments not
+/// matching their types, are kept as plain objects, so serializing the
+/// expression gives back the same JSON.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// # use serde_json::json;
+/// #
+/// let expr: Expr = serde_json::from_value(json!({
+///     "lambda": "cat",
+///     "expr": { "select": ["data", "name"], "from": { "var": "cat" } }
+/// })).unwrap();
+///
+/// match expr {
+///     Expr::Query(query) => match *query {
+///         Query::Lambda(lambda) => {
+///             assert_eq!(json!("cat"), serde_json::to_value(lambda.params()).unwrap())
+///         }
+///         _ => panic!("not a lambda"),
+///     },
+///     _ => panic!("not a query"),
+/// }
+/// ```
+impl<'de, 'a> Deserialize<'de> for Expr<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let json = serde_json::Value::deserialize(deserializer)?;
+        Expr::from_json(json).map_err(de::Error::custom)
+    }
+}
+
+impl<'a> Expr<'a> {
+    pub(crate) fn from_json(json: serde_json::Value) -> Result<Self, serde_json::Error> {
+        use serde_json::Value as Json;
+
+        match json {
+            Json::Null => Ok(Expr::null()),
+            Json::Bool(b) => Ok(Expr::from(b)),
+            Json::Number(n) => Ok(Expr::from(serde_json::from_value::<Number>(Json::Number(
+                n,
+            ))?)),
+            Json::String(s) => Ok(Expr::from(s)),
+            Json::Array(items) => {
+                let exprs = items
+                    .into_iter()
+                    .map(Expr::from_json)
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(Expr::from(Array::from(exprs)))
+            }
+            Json::Object(obj) => Expr::from_json_object(obj),
+        }
+    }
+
+    fn from_json_object(
+        mut obj: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Self, serde_json::Error> {
+        use serde_json::Value as Json;
+
+        if obj.len() == 1 {
+            match obj.keys().next().map(String::as_str) {
+                Some("@query") => {
+                    let query = obj.remove("@query").unwrap();
+                    return Ok(Expr::from_json(query)?.into_quoted());
+                }
+                Some("@ref") | Some("@ts") | Some("@date") | Some("@bytes") | Some("@set") => {
+                    let value: Value = serde_json::from_value(Json::Object(obj))?;
+                    return Ok(Expr::from(value));
+                }
+                Some("object") if obj["object"].is_object() => {
+                    if let Some(Json::Object(fields)) = obj.remove("object") {
+                        let fields = Expr::object_from_json(fields)?;
+                        return Ok(Expr::Annotated(AnnotatedExpr::Object(Box::new(fields))));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let json = Json::Object(obj);
+        let query = Query::from_json(&json);
+
+        match (query, json) {
+            (Ok(query), _) => Ok(Expr::from(query)),
+            (Err(_), Json::Object(obj)) => {
+                let fields = Expr::object_from_json(obj)?;
+                Ok(Expr::Simple(SimpleExpr::Object(Box::new(fields))))
+            }
+            (Err(e), _) => Err(e),
+        }
+    }
+
+    fn object_from_json(
+        obj: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Object<'a>, serde_json::Error> {
+        let fields = obj
+            .into_iter()
+            .map(|(k, v)| Ok((Cow::from(k), Expr::from_json(v)?)))
+            .collect::<Result<BTreeMap<_, _>, serde_json::Error>>()?;
+
+        Ok(Object::from(fields))
+    }
+}
+
 impl<'a> From<DateTime<Utc>> for Expr<'a> {
     fn from(dt: DateTime<Utc>) -> Expr<'a> {
         Expr::Annotated(AnnotatedExpr::Timestamp(dt))
@@ -456,6 +681,21 @@ mod tests {
         assert_eq!("{\"object\":{\"foo\":\"bar\",\"lol\":false}}", serialized)
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_reuse() {
+        let mut inner = Object::default();
+        inner.insert("name", "Musti");
+
+        let unannotated = Expr::Simple(SimpleExpr::Object(Box::new(inner)));
+        let reused = Array::from(vec![unannotated]).reuse();
+
+        assert_eq!(
+            json!([{ "object": { "name": "Musti" } }]),
+            serde_json::to_value(&Expr::from(reused)).unwrap()
+        );
+    }
+
     #[test]
     fn test_set_expr() {
         let set = Set::matching(Ref::index("cats_age"), 8);
@@ -466,7 +706,7 @@ mod tests {
             "@set": {
                 "match": {
                     "@ref": {
-                        "index": {
+                        "class": {
                             "@ref": {
                                 "id": "indexes"
                             }
@@ -495,4 +735,163 @@ mod tests {
 
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn test_value_round_trip() {
+        let value: Value = serde_json::from_value(json!({
+            "name": "Musti",
+            "age": 7,
+            "weight": 4.5,
+            "cute": true,
+            "friend": null,
+            "nicknames": ["mustu", { "short": "mu" }],
+            "owner": {
+                "@ref": {
+                    "class": {
+                        "@ref": {
+                            "class": { "@ref": { "id": "classes" } },
+                            "id": "owners"
+                        }
+                    },
+                    "id": "1"
+                }
+            },
+            "bytes": { "@bytes": "AQID" },
+            "birthday": { "@date": "2011-07-07" },
+            "created_at": { "@ts": "1970-01-01T00:01:00Z" },
+            "friends": {
+                "@set": {
+                    "union": [
+                        { "@set": { "match": { "@ref": { "id": "cats_by_age" } }, "terms": 7 } },
+                        { "@set": { "match": { "@ref": { "id": "all_cats" } } } }
+                    ]
+                }
+            },
+            "greet": {
+                "@query": {
+                    "lambda": "x",
+                    "expr": { "object": { "hello": { "var": "x" } } }
+                }
+            }
+        }))
+        .unwrap();
+
+        let serialized = serde_json::to_value(&Expr::from(value)).unwrap();
+
+        let expected = json!({
+            "object": {
+                "name": "Musti",
+                "age": 7,
+                "weight": 4.5,
+                "cute": true,
+                "friend": null,
+                "nicknames": ["mustu", { "object": { "short": "mu" } }],
+                "owner": {
+                    "@ref": {
+                        "class": {
+                            "@ref": {
+                                "class": { "@ref": { "id": "classes" } },
+                                "id": "owners"
+                            }
+                        },
+                        "id": "1"
+                    }
+                },
+                "bytes": { "@bytes": "AQID" },
+                "birthday": { "@date": "2011-07-07" },
+                "created_at": { "@ts": "1970-01-01T00:01:00Z" },
+                "friends": {
+                    "@set": {
+                        "union": [
+                            { "@set": { "match": { "@ref": { "id": "cats_by_age" } }, "terms": 7 } },
+                            { "@set": { "match": { "@ref": { "id": "all_cats" } } } }
+                        ]
+                    }
+                },
+                "greet": {
+                    "@query": {
+                        "lambda": "x",
+                        "expr": { "object": { "hello": { "var": "x" } } }
+                    }
+                }
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_deserialize_function_body() {
+        let body = json!({
+            "lambda": "name",
+            "expr": {
+                "create": { "@ref": { "class": { "@ref": { "id": "classes" } }, "id": "cats" } },
+                "params": {
+                    "object": {
+                        "data": {
+                            "object": {
+                                "name": { "replacestr": { "var": "name" }, "find": "_", "replace": " " },
+                                "born": { "@date": "2011-07-07" },
+                                "tags": ["cute", { "@ts": "1970-01-01T00:01:00Z" }]
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let value: Value = serde_json::from_value(json!({ "@query": body })).unwrap();
+        let stored = value.as_query().unwrap().clone();
+
+        let query: Query = crate::from_value(stored).unwrap();
+
+        match query {
+            Query::Lambda(_) => (),
+            query => panic!("expected a lambda, got {:?}", query),
+        }
+
+        assert_eq!(body, serde_json::to_value(&query).unwrap());
+
+        let quoted: Expr = crate::from_value(value).unwrap();
+
+        assert_eq!(
+            json!({ "@query": body }),
+            serde_json::to_value(&quoted).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_unknown_objects() {
+        let json = json!({
+            "paginate": { "@set": { "match": { "@ref": { "class": { "@ref": { "id": "indexes" } }, "id": "all_cats" } } } },
+            "size": 10
+        });
+
+        let expr: Expr = serde_json::from_value(json.clone()).unwrap();
+
+        match expr {
+            Expr::Query(ref query) => match **query {
+                Query::Paginate(_) => (),
+                ref query => panic!("expected paginate, got {:?}", query),
+            },
+            ref expr => panic!("expected a query, got {:?}", expr),
+        }
+
+        let serialized = serde_json::to_value(&expr).unwrap();
+
+        assert_eq!(json["paginate"], serialized["paginate"]);
+        assert_eq!(json!(10), serialized["size"]);
+        assert_eq!(json!(false), serialized["events"]);
+
+        let not_a_function = json!({ "meow": [1, { "var": "x" }] });
+        let expr: Expr = serde_json::from_value(not_a_function.clone()).unwrap();
+
+        assert_eq!(not_a_function, serde_json::to_value(&expr).unwrap());
+        assert!(serde_json::from_value::<Query>(not_a_function).is_err());
+
+        let computed_name = json!({ "create_class": { "object": { "name": { "var": "name" } } } });
+        let expr: Expr = serde_json::from_value(computed_name.clone()).unwrap();
+
+        assert_eq!(computed_name, serde_json::to_value(&expr).unwrap());
+    }
 }
@@ -1,16 +1,21 @@
 use super::Expr;
 use std::borrow::Cow;
 
-#[derive(Debug, Clone, Serialize)]
+into_owned! {
+    Array { 0 },
+    Bytes { 0 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Array<'a>(pub Vec<Expr<'a>>);
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Bytes<'a>(pub Cow<'a, [u8]>);
 
 impl<'a> Array<'a> {
+    #[deprecated(note = "use Expr::from(Value)")]
     pub fn reuse(self) -> Self {
-        let reused = self.0.into_iter().map(|e| e.reuse()).collect();
-        Array(reused)
+        Array(self.0.into_iter().map(Expr::reannotate).collect())
     }
 
     pub fn push(&mut self, e: impl Into<Expr<'a>>) -> &mut Self {
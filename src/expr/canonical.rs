@@ -0,0 +1,578 @@
+//! A canonical serialization of queries, for caching and comparing them
+//! between processes.
+use super::Expr;
+use crate::query::Query;
+use serde::{ser, Serialize};
+use serde_json::{Map, Value as Json};
+use std::fmt::Write;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+impl<'a> Expr<'a> {
+    /// The expression in the wire format with the object keys sorted and no
+    /// whitespace, so identical expressions always produce identical bytes.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// #
+    /// let mut params = Object::default();
+    /// params.insert("name", "Musti");
+    /// params.insert("age", 7);
+    ///
+    /// let query = Expr::from(Create::new(Ref::class("cats"), params));
+    ///
+    /// assert_eq!(
+    ///     r#"{"create":{"@ref":{"class":{"@ref":{"id":"classes"}},"id":"cats"}},"params":{"object":{"data":{"object":{"age":7,"name":"Musti"}}}}}"#,
+    ///     query.to_canonical_json(),
+    /// );
+    /// ```
+    pub fn to_canonical_json(&self) -> String {
+        canonical_json(self)
+    }
+
+    /// A 64-bit FNV-1a hash of the canonical JSON. Unlike the `Hash`
+    /// implementation, the fingerprint is the same in every process and
+    /// platform, and can be stored as a cache key.
+    pub fn fingerprint(&self) -> u64 {
+        fingerprint(&self.to_canonical_json())
+    }
+}
+
+impl<'a> Query<'a> {
+    /// The query in the wire format with the object keys sorted and no
+    /// whitespace.
+    pub fn to_canonical_json(&self) -> String {
+        canonical_json(self)
+    }
+
+    /// A 64-bit FNV-1a hash of the canonical JSON.
+    pub fn fingerprint(&self) -> u64 {
+        fingerprint(&self.to_canonical_json())
+    }
+}
+
+fn canonical_json<T: Serialize + ?Sized>(query: &T) -> String {
+    let json = query
+        .serialize(CanonicalSerializer)
+        .expect("Queries serialize to JSON");
+    let mut out = String::new();
+
+    write_json(&json, &mut out);
+    out
+}
+
+fn write_json(json: &Json, out: &mut String) {
+    match json {
+        Json::Array(items) => {
+            out.push('[');
+
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+
+                write_json(item, out);
+            }
+
+            out.push(']');
+        }
+        Json::Object(obj) => {
+            let mut keys: Vec<&String> = obj.keys().collect();
+            keys.sort();
+
+            out.push('{');
+
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+
+                write!(out, "{}:", Json::from(key.as_str())).unwrap();
+                write_json(&obj[key], out);
+            }
+
+            out.push('}');
+        }
+        // `-0.0` equals `0.0`, so it must have the same fingerprint.
+        Json::Number(n) if n.as_f64() == Some(0.0) && n.is_f64() => out.push_str("0.0"),
+        scalar => write!(out, "{}", scalar).unwrap(),
+    }
+}
+
+/// Writes a non-finite float, which JSON has no number for, in a form that
+/// can't be confused with `null` or any other value.
+fn non_finite(f: f64) -> Json {
+    let name = if f.is_nan() {
+        "NaN"
+    } else if f > 0.0 {
+        "Infinity"
+    } else {
+        "-Infinity"
+    };
+
+    let mut obj = Map::new();
+    obj.insert(String::from("@float"), Json::from(name));
+
+    Json::Object(obj)
+}
+
+/// Serializes into JSON like `serde_json::to_value`, except for the
+/// non-finite floats `serde_json` turns into `null`.
+struct CanonicalSerializer;
+
+impl ser::Serializer for CanonicalSerializer {
+    type Ok = Json;
+    type Error = serde_json::Error;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = VariantSerializer<SeqSerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = VariantSerializer<MapSerializer>;
+
+    fn serialize_bool(self, v: bool) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Json, Self::Error> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Json, Self::Error> {
+        if v.is_finite() {
+            Ok(Json::from(v))
+        } else {
+            Ok(non_finite(v))
+        }
+    }
+
+    fn serialize_char(self, v: char) -> Result<Json, Self::Error> {
+        Ok(Json::from(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Json, Self::Error> {
+        Ok(Json::from(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Json, Self::Error> {
+        Ok(Json::from(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Json, Self::Error> {
+        Ok(Json::Null)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Json, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Json, Self::Error> {
+        Ok(Json::Null)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Json, Self::Error> {
+        Ok(Json::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Json, Self::Error> {
+        Ok(Json::from(variant))
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<Json, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Json, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(wrap_variant(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, Self::Error> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<SeqSerializer>, Self::Error> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<MapSerializer, Self::Error> {
+        Ok(MapSerializer {
+            obj: Map::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<MapSerializer, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<VariantSerializer<MapSerializer>, Self::Error> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+fn wrap_variant(variant: &str, json: Json) -> Json {
+    let mut obj = Map::new();
+    obj.insert(String::from(variant), json);
+
+    Json::Object(obj)
+}
+
+struct SeqSerializer {
+    items: Vec<Json>,
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Json;
+    type Error = serde_json::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.items.push(value.serialize(CanonicalSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Json, Self::Error> {
+        Ok(Json::Array(self.items))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Json;
+    type Error = serde_json::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Json, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Json;
+    type Error = serde_json::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Json, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    obj: Map<String, Json>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Json;
+    type Error = serde_json::Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        match key.serialize(CanonicalSerializer)? {
+            Json::String(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(ser::Error::custom("key must be a string")),
+        }
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("value without a key"))?;
+
+        self.obj.insert(key, value.serialize(CanonicalSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Json, Self::Error> {
+        Ok(Json::Object(self.obj))
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Json;
+    type Error = serde_json::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.obj
+            .insert(String::from(key), value.serialize(CanonicalSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Json, Self::Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<SeqSerializer> {
+    type Ok = Json;
+    type Error = serde_json::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Json, Self::Error> {
+        let inner = ser::SerializeSeq::end(self.inner)?;
+        Ok(wrap_variant(self.variant, inner))
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<MapSerializer> {
+    type Ok = Json;
+    type Error = serde_json::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Json, Self::Error> {
+        let inner = ser::SerializeMap::end(self.inner)?;
+        Ok(wrap_variant(self.variant, inner))
+    }
+}
+
+fn fingerprint(canonical: &str) -> u64 {
+    canonical.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fingerprint;
+    use crate::prelude::*;
+    use serde_json::{self, json};
+    use std::collections::HashSet;
+
+    fn cat_query(name: &str) -> Expr<'_> {
+        Expr::from(If::cond(
+            Exists::new(Ref::instance(name)),
+            Get::instance(Ref::instance(name)),
+            Expr::null(),
+        ))
+    }
+
+    #[test]
+    fn test_structural_equality() {
+        let owned = String::from("musti");
+
+        assert_eq!(cat_query("musti"), cat_query(&owned));
+        assert_ne!(cat_query("musti"), cat_query("naukio"));
+
+        let parsed = crate::fql::parse(r#"If(Exists(Ref("musti")), Get(Ref("musti")), null)"#);
+        assert_eq!(cat_query("musti"), parsed.unwrap());
+
+        let queries: HashSet<Expr> =
+            vec![cat_query("musti"), cat_query(&owned), cat_query("naukio")]
+                .into_iter()
+                .collect();
+
+        assert_eq!(2, queries.len());
+    }
+
+    #[test]
+    fn test_number_equality() {
+        assert_eq!(Expr::from(1), Expr::from(1u64));
+        assert_eq!(Expr::from(0.5f32), Expr::from(0.5));
+        assert_eq!(Expr::from(0.0), Expr::from(-0.0));
+        assert_eq!(Expr::from(std::f64::NAN), Expr::from(std::f64::NAN));
+        assert_ne!(Expr::from(1), Expr::from(1.0));
+        assert_ne!(Expr::from(1), Expr::from(1.5));
+
+        let pairs = vec![
+            (Expr::from(1), Expr::from(1u64)),
+            (Expr::from(0.5f32), Expr::from(0.5)),
+            (Expr::from(0.0), Expr::from(-0.0)),
+        ];
+
+        for (a, b) in pairs {
+            assert_eq!(a.fingerprint(), b.fingerprint());
+        }
+
+        assert_ne!(Expr::from(1).fingerprint(), Expr::from(1.0).fingerprint());
+
+        let numbers: HashSet<Expr> = vec![Expr::from(1), Expr::from(1u64), Expr::from(1.0)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(2, numbers.len());
+    }
+
+    #[test]
+    fn test_non_finite_floats() {
+        let nan = Expr::from(std::f64::NAN);
+        let infinity = Expr::from(std::f64::INFINITY);
+        let negative = Expr::from(-std::f32::INFINITY);
+
+        assert_eq!(r#"{"@float":"NaN"}"#, nan.to_canonical_json());
+        assert_eq!(r#"{"@float":"Infinity"}"#, infinity.to_canonical_json());
+        assert_eq!(r#"{"@float":"-Infinity"}"#, negative.to_canonical_json());
+        assert_eq!("null", Expr::null().to_canonical_json());
+
+        let fingerprints: HashSet<u64> = vec![&nan, &infinity, &negative, &Expr::null()]
+            .into_iter()
+            .map(Expr::fingerprint)
+            .collect();
+
+        assert_eq!(4, fingerprints.len());
+        assert_eq!(nan.fingerprint(), Expr::from(-std::f64::NAN).fingerprint());
+    }
+
+    #[test]
+    fn test_canonical_json() {
+        let raw: Expr = serde_json::from_value(json!({
+            "paginate": { "match": { "index": "cats" }, "terms": "musti" },
+            "size": 10,
+            "sources": true
+        }))
+        .unwrap();
+
+        let mut paginate = Paginate::new(Match::new(Index::find("cats")).with_terms("musti"));
+        paginate.size(10).sources(true);
+
+        let built = Expr::from(paginate);
+
+        assert_eq!(
+            r#"{"events":false,"paginate":{"match":{"index":"cats"},"terms":"musti"},"size":10,"sources":true}"#,
+            built.to_canonical_json()
+        );
+
+        assert_eq!(raw.to_canonical_json(), built.to_canonical_json());
+        assert_eq!(raw.fingerprint(), built.fingerprint());
+        assert_ne!(built.fingerprint(), cat_query("musti").fingerprint());
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, fingerprint(""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fingerprint("a"));
+
+        let add = Query::from(Add::new(Array::from(vec![1, 2])));
+
+        assert_eq!(r#"{"add":[1,2]}"#, add.to_canonical_json());
+        assert_eq!(0x11cb_98e4_b9d2_79bf, add.fingerprint());
+    }
+}
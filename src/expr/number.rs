@@ -1,6 +1,17 @@
 use crate::expr::{Expr, SimpleExpr};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+/// A Fauna number, either an integer or a floating point value.
+///
+/// Numbers are compared by their numeric value, regardless of the
+/// representation: `Int(1)`, `UInt(1)` and `Double(1.0)` are equal, and
+/// `-0.0` equals `0.0`. Integers and floats are compared exactly, without
+/// rounding the integer to a float. `NaN` equals itself and is greater than
+/// any other number, making the order total.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Number {
     UInt(u64),
@@ -67,6 +78,133 @@ impl Number {
     }
 }
 
+/// A number as it is written in a query. Unlike `Number`, integers and floats
+/// are never equal, and floats are compared by their bits with `-0.0` and
+/// `NaN` normalized.
+#[derive(PartialEq, Eq, Hash)]
+pub(crate) enum WireNumber {
+    Int(i128),
+    Float(u64),
+}
+
+/// The bits of the quiet `NaN` all the `NaN`s are normalized to.
+const QUIET_NAN: u64 = 0x7ff8_0000_0000_0000;
+
+impl Number {
+    pub(crate) fn wire(self) -> WireNumber {
+        let f = match self {
+            Number::UInt(u) => return WireNumber::Int(i128::from(u)),
+            Number::Int(i) => return WireNumber::Int(i128::from(i)),
+            Number::Double(f) => f,
+            Number::Float(f) => f64::from(f),
+        };
+
+        let bits = if f.is_nan() {
+            QUIET_NAN
+        } else if f == 0.0 {
+            0
+        } else {
+            f.to_bits()
+        };
+
+        WireNumber::Float(bits)
+    }
+}
+
+/// A number in a form that can be compared exactly.
+#[derive(Clone, Copy)]
+enum Canonical {
+    Int(i128),
+    Float(f64),
+}
+
+/// 2^127 as a float, the first float out of the `i128` range.
+const I128_BOUND: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+
+impl Number {
+    fn canonical(self) -> Canonical {
+        let f = match self {
+            Number::UInt(u) => return Canonical::Int(i128::from(u)),
+            Number::Int(i) => return Canonical::Int(i128::from(i)),
+            Number::Double(f) => f,
+            Number::Float(f) => f64::from(f),
+        };
+
+        if f.fract() == 0.0 && (-I128_BOUND..I128_BOUND).contains(&f) {
+            Canonical::Int(f as i128)
+        } else {
+            Canonical::Float(f)
+        }
+    }
+}
+
+/// Compares an integer to a float that is not integral.
+fn cmp_int_float(i: i128, f: f64) -> Ordering {
+    if f.is_nan() || f >= I128_BOUND {
+        Ordering::Less
+    } else if f < -I128_BOUND {
+        Ordering::Greater
+    } else {
+        let floor = f.floor();
+
+        match i.cmp(&(floor as i128)) {
+            // f has a fractional part, so it's larger than its floor.
+            Ordering::Equal => Ordering::Less,
+            ord => ord,
+        }
+    }
+}
+
+fn cmp_floats(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.canonical(), other.canonical()) {
+            (Canonical::Int(a), Canonical::Int(b)) => a.cmp(&b),
+            (Canonical::Int(a), Canonical::Float(b)) => cmp_int_float(a, b),
+            (Canonical::Float(a), Canonical::Int(b)) => cmp_int_float(b, a).reverse(),
+            (Canonical::Float(a), Canonical::Float(b)) => cmp_floats(a, b),
+        }
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Number {}
+
+impl Hash for Number {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.canonical() {
+            Canonical::Int(i) => {
+                0u8.hash(state);
+                i.hash(state);
+            }
+            Canonical::Float(f) if f.is_nan() => 1u8.hash(state),
+            Canonical::Float(f) => {
+                2u8.hash(state);
+                f.to_bits().hash(state);
+            }
+        }
+    }
+}
+
 int_expr!(i8, i16, i32, i64);
 uint_expr!(u8, u16, u32, u64);
 
@@ -81,3 +219,68 @@ impl<'a> From<f32> for Number {
         Number::Float(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Number;
+    use std::{cmp::Ordering, collections::HashSet, f64};
+
+    #[test]
+    fn test_cross_type_equality() {
+        assert_eq!(Number::Int(1), Number::UInt(1));
+        assert_eq!(Number::Int(1), Number::Double(1.0));
+        assert_eq!(Number::Float(0.5), Number::Double(0.5));
+        assert_eq!(Number::Double(-0.0), Number::Int(0));
+        assert_ne!(Number::Int(-1), Number::UInt(u64::max_value()));
+    }
+
+    #[test]
+    fn test_exact_int_float_comparison() {
+        let big = Number::UInt(u64::max_value());
+        let rounded = Number::Double(u64::max_value() as f64);
+
+        assert_eq!(Ordering::Less, big.cmp(&rounded));
+        assert_eq!(Ordering::Less, Number::Int(1).cmp(&Number::Double(1.5)));
+        assert_eq!(
+            Ordering::Greater,
+            Number::Int(-1).cmp(&Number::Double(-1.5))
+        );
+        assert_eq!(
+            Ordering::Less,
+            Number::Int(i64::max_value()).cmp(&Number::Double(1e300))
+        );
+        assert_eq!(
+            Ordering::Greater,
+            Number::Int(i64::min_value()).cmp(&Number::Double(-1e300))
+        );
+    }
+
+    #[test]
+    fn test_nan_and_infinity() {
+        let nan = Number::Double(f64::NAN);
+
+        assert_eq!(nan, Number::Float(std::f32::NAN));
+        assert_eq!(Ordering::Greater, nan.cmp(&Number::Double(f64::INFINITY)));
+        assert_eq!(Ordering::Greater, nan.cmp(&Number::UInt(u64::max_value())));
+        assert_eq!(
+            Ordering::Less,
+            Number::Double(f64::NEG_INFINITY).cmp(&Number::Int(i64::min_value()))
+        );
+    }
+
+    #[test]
+    fn test_hash_matches_equality() {
+        let mut set = HashSet::new();
+
+        set.insert(Number::Int(1));
+        set.insert(Number::UInt(1));
+        set.insert(Number::Double(1.0));
+        set.insert(Number::Double(-0.0));
+        set.insert(Number::Int(0));
+        set.insert(Number::Double(f64::NAN));
+        set.insert(Number::Float(std::f32::NAN));
+        set.insert(Number::Double(0.5));
+
+        assert_eq!(4, set.len());
+    }
+}
@@ -1,7 +1,11 @@
-use crate::expr::Expr;
+use crate::{expr::Expr, fql};
 use std::{borrow::Cow, collections::BTreeMap, fmt};
 
-#[derive(Debug, Serialize, Clone, Default)]
+into_owned! {
+    Object { 0 },
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Object<'a>(pub(crate) BTreeMap<Cow<'a, str>, Expr<'a>>);
 
 impl<'a> From<BTreeMap<Cow<'a, str>, Expr<'a>>> for Object<'a> {
@@ -11,11 +15,13 @@ impl<'a> From<BTreeMap<Cow<'a, str>, Expr<'a>>> for Object<'a> {
 }
 
 impl<'a> Object<'a> {
-    pub fn insert<E>(&mut self, key: &'a str, val: E) -> &mut Self
+    /// Sets the value of the key, borrowed or owned.
+    pub fn insert<K, E>(&mut self, key: K, val: E) -> &mut Self
     where
+        K: Into<Cow<'a, str>>,
         E: Into<Expr<'a>>,
     {
-        self.0.insert(Cow::from(key), val.into());
+        self.0.insert(key.into(), val.into());
         self
     }
 
@@ -27,16 +33,22 @@ impl<'a> Object<'a> {
         self.0.is_empty()
     }
 
+    #[deprecated(note = "use Expr::from(Value)")]
     pub fn reuse(self) -> Self {
-        let reused = self.0.into_iter().map(|(k, v)| (k, v.reuse())).collect();
-        Object(reused)
+        Object(
+            self.0
+                .into_iter()
+                .map(|(k, v)| (k, v.reannotate()))
+                .collect(),
+        )
     }
 }
 
 impl<'a> fmt::Display for Object<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let pairs: Vec<String> = self.0.iter().map(|(k, v)| format!("{}:{}", k, v)).collect();
+        let mut annotated = BTreeMap::new();
+        annotated.insert("object", self);
 
-        write!(f, "{{{}}}", pairs.join(","))
+        fql::write_query(&annotated, f)
     }
 }
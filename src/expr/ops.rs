@@ -0,0 +1,229 @@
+//! Operators and fluent methods for combining expressions.
+//!
+//! Chains of the same function are flattened into one call, so `a + b + c`
+//! builds `Add([a, b, c])` instead of `Add([Add([a, b]), c])`. Comparisons are
+//! never flattened, `(a < b) < c` compares a boolean.
+use super::{Array, Expr, SimpleExpr};
+use crate::query::{
+    logical::{And, Equals, Gt, Gte, Lt, Lte, Not, Or},
+    math::{Add, BitAnd, BitOr, BitXor, Divide, Modulo, Multiply, Subtract},
+    read::{Get, Select},
+    Query,
+};
+use std::ops;
+
+macro_rules! math_op {
+    ($($op:ident::$method:ident => $function:ident.$field:ident),* $(,)?) => {
+        $(
+            impl<'a, E: Into<Expr<'a>>> ops::$op<E> for Expr<'a> {
+                type Output = Expr<'a>;
+
+                fn $method(self, rhs: E) -> Expr<'a> {
+                    let mut args = match self {
+                        Expr::Query(query) => match *query {
+                            Query::$function($function {
+                                $field: Expr::Simple(SimpleExpr::Array(array)),
+                            }) => array.0,
+                            query => vec![Expr::Query(Box::new(query))],
+                        },
+                        expr => vec![expr],
+                    };
+
+                    args.push(rhs.into());
+
+                    Expr::from($function::new(Array::from(args)))
+                }
+            }
+        )*
+    };
+}
+
+math_op! {
+    Add::add => Add.add,
+    Sub::sub => Subtract.subtract,
+    Mul::mul => Multiply.multiply,
+    Div::div => Divide.divide,
+    Rem::rem => Modulo.modulo,
+    BitAnd::bitand => BitAnd.bitand,
+    BitOr::bitor => BitOr.bitor,
+    BitXor::bitxor => BitXor.bitxor,
+}
+
+impl<'a> ops::Not for Expr<'a> {
+    type Output = Expr<'a>;
+
+    fn not(self) -> Expr<'a> {
+        Expr::from(Not::new(self))
+    }
+}
+
+impl<'a> Expr<'a> {
+    /// `Equals(self, other)`. Not named `eq`, which would be shadowed by
+    /// `PartialEq::eq` when called on a reference.
+    pub fn equals(self, other: impl Into<Expr<'a>>) -> Expr<'a> {
+        Expr::from(Equals::new(self, other))
+    }
+
+    /// `LT(self, other)`.
+    pub fn lt(self, other: impl Into<Expr<'a>>) -> Expr<'a> {
+        Expr::from(Lt::new(self, other))
+    }
+
+    /// `LTE(self, other)`.
+    pub fn lte(self, other: impl Into<Expr<'a>>) -> Expr<'a> {
+        Expr::from(Lte::new(self, other))
+    }
+
+    /// `GT(self, other)`.
+    pub fn gt(self, other: impl Into<Expr<'a>>) -> Expr<'a> {
+        Expr::from(Gt::new(self, other))
+    }
+
+    /// `GTE(self, other)`.
+    pub fn gte(self, other: impl Into<Expr<'a>>) -> Expr<'a> {
+        Expr::from(Gte::new(self, other))
+    }
+
+    /// `And(self, other)`, extending the expression if it's already an `And`.
+    pub fn and(self, other: impl Into<Expr<'a>>) -> Expr<'a> {
+        let mut and = match self {
+            Expr::Query(query) => match *query {
+                Query::And(And { and }) => and,
+                query => vec![Expr::Query(Box::new(query))],
+            },
+            expr => vec![expr],
+        };
+
+        and.push(other.into());
+
+        Expr::from(And::from(and))
+    }
+
+    /// `Or(self, other)`, extending the expression if it's already an `Or`.
+    pub fn or(self, other: impl Into<Expr<'a>>) -> Expr<'a> {
+        let mut or = match self {
+            Expr::Query(query) => match *query {
+                Query::Or(Or { or }) => or,
+                query => vec![Expr::Query(Box::new(query))],
+            },
+            expr => vec![expr],
+        };
+
+        or.push(other.into());
+
+        Expr::from(Or::from(or))
+    }
+
+    /// `Select(path, self)`.
+    pub fn select(self, path: impl Into<Array<'a>>) -> Expr<'a> {
+        Expr::from(Select::new(path, self))
+    }
+
+    /// `Get(self)`, reading the instance the expression refers to.
+    pub fn get(self) -> Expr<'a> {
+        Expr::from(Get::instance(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    fn var(name: &'static str) -> Expr<'static> {
+        Expr::from(Var::new(name))
+    }
+
+    #[test]
+    fn test_math_operators_flatten() {
+        let expr = var("a") + 1 + var("b") - 2;
+
+        assert_eq!(
+            json!({ "subtract": [{ "add": [{ "var": "a" }, 1, { "var": "b" }] }, 2] }),
+            serde_json::to_value(&expr).unwrap()
+        );
+
+        let expr = Expr::from(2) * 3 * 4 / 5 % 6;
+
+        assert_eq!(
+            json!({ "modulo": [{ "divide": [{ "multiply": [2, 3, 4] }, 5] }, 6] }),
+            serde_json::to_value(&expr).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_math_operators_keep_non_array_calls() {
+        let expr = Expr::from(Add::new(var("xs"))) + 1;
+
+        assert_eq!(
+            json!({ "add": [{ "add": { "var": "xs" } }, 1] }),
+            serde_json::to_value(&expr).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bit_operators() {
+        let expr = (Expr::from(1) & 2 & 3) | (Expr::from(4) ^ 5);
+
+        assert_eq!(
+            json!({ "bitor": [{ "bitand": [1, 2, 3] }, { "bitxor": [4, 5] }] }),
+            serde_json::to_value(&expr).unwrap()
+        );
+
+        assert_eq!(
+            json!({ "not": true }),
+            serde_json::to_value(&!Expr::from(true)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let expr = var("age")
+            .gte(18)
+            .and(var("age").lt(65))
+            .and(var("name").equals("Musti"))
+            .or(var("admin"));
+
+        assert_eq!(
+            json!({
+                "or": [
+                    {
+                        "and": [
+                            { "gte": [{ "var": "age" }, 18] },
+                            { "lt": [{ "var": "age" }, 65] },
+                            { "equals": [{ "var": "name" }, "Musti"] }
+                        ]
+                    },
+                    { "var": "admin" }
+                ]
+            }),
+            serde_json::to_value(&expr).unwrap()
+        );
+
+        assert_eq!(
+            json!({ "lt": [{ "lt": [1, 2] }, 3] }),
+            serde_json::to_value(&Expr::from(1).lt(2).lt(3)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read() {
+        let expr = Expr::from(Ref::instance("musti"))
+            .get()
+            .select(vec!["data", "age"])
+            .gt(var("min"));
+
+        assert_eq!(
+            json!({
+                "gt": [
+                    {
+                        "select": ["data", "age"],
+                        "from": { "get": { "@ref": { "id": "musti" } } }
+                    },
+                    { "var": "min" }
+                ]
+            }),
+            serde_json::to_value(&expr).unwrap()
+        );
+    }
+}
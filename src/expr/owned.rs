@@ -0,0 +1,181 @@
+//! Converting borrowed expressions into owned ones.
+use super::{Number, SimpleExpr};
+use crate::{
+    expr::SimpleLevel,
+    query::{
+        datetime::EpochUnit,
+        string::Normalizer,
+        write::{Action, Role},
+    },
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::{borrow::Cow, collections::BTreeMap};
+
+/// Converts a value into one owning all of its data, with a `'static`
+/// lifetime, for storing queries or sending them to another thread.
+///
+/// Implemented for `Expr`, `Query` and all the query and parameter structs.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// #
+/// fn by_name(name: &str) -> Expr<'static> {
+///     let mut params = Object::default();
+///     params.insert("name", name);
+///
+///     Expr::from(Create::new(Ref::class("cats"), params)).into_owned()
+/// }
+///
+/// let query = std::thread::spawn(|| by_name(&String::from("Musti")))
+///     .join()
+///     .unwrap();
+/// ```
+pub trait IntoOwned {
+    /// The owned version of the type.
+    type Owned: 'static;
+
+    fn into_owned(self) -> Self::Owned;
+}
+
+impl<'a, B> IntoOwned for Cow<'a, B>
+where
+    B: ToOwned + ?Sized + 'static,
+{
+    type Owned = Cow<'static, B>;
+
+    fn into_owned(self) -> Cow<'static, B> {
+        Cow::Owned(Cow::into_owned(self))
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Box<T> {
+    type Owned = Box<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Box::new((*self).into_owned())
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Vec<T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter().map(IntoOwned::into_owned).collect()
+    }
+}
+
+impl<K, V> IntoOwned for BTreeMap<K, V>
+where
+    K: IntoOwned,
+    K::Owned: Ord,
+    V: IntoOwned,
+{
+    type Owned = BTreeMap<K::Owned, V::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.into_iter()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+}
+
+macro_rules! already_owned {
+    ($($kind:ty),*) => (
+        $(
+            impl IntoOwned for $kind {
+                type Owned = $kind;
+
+                fn into_owned(self) -> $kind {
+                    self
+                }
+            }
+        )*
+    );
+}
+
+already_owned!(
+    bool,
+    u16,
+    u32,
+    u64,
+    Number,
+    NaiveDate,
+    DateTime<Utc>,
+    SimpleLevel,
+    EpochUnit,
+    Normalizer,
+    Action,
+    Role
+);
+
+impl<'a> IntoOwned for SimpleExpr<'a> {
+    type Owned = SimpleExpr<'static>;
+
+    fn into_owned(self) -> SimpleExpr<'static> {
+        match self {
+            SimpleExpr::String(s) => SimpleExpr::String(IntoOwned::into_owned(s)),
+            SimpleExpr::Number(n) => SimpleExpr::Number(n),
+            SimpleExpr::Boolean(b) => SimpleExpr::Boolean(b),
+            SimpleExpr::Array(a) => SimpleExpr::Array(a.into_owned()),
+            SimpleExpr::Object(o) => SimpleExpr::Object(o.into_owned()),
+            SimpleExpr::Null => SimpleExpr::Null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    fn owned(name: &str) -> Expr<'static> {
+        let mut data = Object::default();
+        data.insert(format!("{}_name", name), name);
+
+        let set = Match::new(Index::find(name)).with_terms(name);
+
+        let query = Array::from(vec![
+            Expr::from(Do::new(Create::new(Ref::class(name), data))),
+            Expr::from(Paginate::new(set)),
+        ]);
+
+        Expr::from(query).into_owned()
+    }
+
+    #[test]
+    fn test_into_owned() {
+        let name = String::from("cats");
+        let query = owned(&name);
+        drop(name);
+
+        let serialized = std::thread::spawn(move || serde_json::to_value(&query).unwrap())
+            .join()
+            .unwrap();
+
+        assert_eq!(
+            json!([
+                {
+                    "do": [{
+                        "create": { "@ref": { "class": { "@ref": { "id": "classes" } }, "id": "cats" } },
+                        "params": { "object": { "data": { "object": { "cats_name": "cats" } } } }
+                    }]
+                },
+                {
+                    "paginate": { "match": { "index": "cats" }, "terms": "cats" },
+                    "size": 64,
+                    "events": false,
+                    "sources": false
+                }
+            ]),
+            serialized
+        );
+    }
+}
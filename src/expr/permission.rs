@@ -1,20 +1,44 @@
 use crate::expr::{Expr, Ref};
 
-#[derive(Serialize, Clone, Copy, Debug)]
+into_owned! {
+    ClassPermissionObject { create, read, write },
+    ClassPermission { object },
+    InstancePermissionObject { read, write },
+    InstancePermission { object },
+    FunctionPermissionObject { call },
+    FunctionPermission { object },
+    IndexPermissionObject { read },
+    IndexPermission { object },
+}
+
+into_owned!(
+    enum AnnotatedLevel {
+        Reference,
+    }
+);
+
+into_owned!(
+    enum Level {
+        Annotated,
+        Simple,
+    }
+);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub enum SimpleLevel {
     #[serde(rename = "public")]
     Public,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 #[doc(hidden)]
 pub enum AnnotatedLevel<'a> {
     Reference(Expr<'a>),
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 /// Permission level definition.
 pub enum Level<'a> {
@@ -39,7 +63,7 @@ impl<'a> Level<'a> {
     }
 }
 
-#[derive(Serialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct ClassPermissionObject<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -50,7 +74,7 @@ pub struct ClassPermissionObject<'a> {
     write: Option<Level<'a>>,
 }
 
-#[derive(Serialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, Hash)]
 /// Creating, reading, and modifying an instance in a class is controlled by the
 /// class’s permissions.
 ///
@@ -79,7 +103,7 @@ impl<'a> ClassPermission<'a> {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct InstancePermissionObject<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -90,7 +114,7 @@ pub struct InstancePermissionObject<'a> {
     write: Option<Level<'a>>,
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 /// An instance also has permissions, which are applied in addition to
 /// permissions defined on its class.
 ///
@@ -113,7 +137,7 @@ impl<'a> InstancePermission<'a> {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct FunctionPermissionObject<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -121,7 +145,7 @@ pub struct FunctionPermissionObject<'a> {
     call: Option<Level<'a>>,
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 /// Calling a function is controlled by its permissions.
 ///
 /// See the [docs](https://docs.fauna.com/fauna/current/reference/security#instance-permissions)
@@ -137,7 +161,7 @@ impl<'a> FunctionPermission<'a> {
     }
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct IndexPermissionObject<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -145,7 +169,7 @@ pub struct IndexPermissionObject<'a> {
     read: Option<Level<'a>>,
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 /// Query access to an index is controlled by its permissions.
 ///
 /// See the [docs](https://docs.fauna.com/fauna/current/reference/security#instance-permissions)
@@ -1,65 +1,114 @@
-use std::{borrow::Cow, fmt};
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-enum RefLocation<'a> {
-    #[serde(rename = "class")]
-    Class {
-        #[serde(rename = "@ref")]
-        location: Box<Ref<'a>>,
-    },
-    #[serde(rename = "class")]
-    Database {
-        #[serde(rename = "@ref")]
-        location: Box<Ref<'a>>,
-    },
-    #[serde(rename = "index")]
-    Index {
-        #[serde(rename = "@ref")]
-        location: Box<Ref<'a>>,
-    },
-    #[serde(rename = "class")]
-    Function {
-        #[serde(rename = "@ref")]
-        location: Box<Ref<'a>>,
-    },
-}
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{borrow::Cow, cmp::Ordering, fmt, str::FromStr};
 
-impl<'a> RefLocation<'a> {
-    fn path(&self) -> String {
-        match self {
-            RefLocation::Class { location } => location.path(),
-            RefLocation::Index { location } => location.path(),
-            RefLocation::Function { location } => location.path(),
-            RefLocation::Database { location } => location.path(),
-        }
-    }
+into_owned! {
+    Ref { id, class, database },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// The ids of the refs Fauna provides for its own classes, such as `keys`.
+/// These refs have no class of their own.
+const NATIVE_IDS: &[&str] = &[
+    "classes",
+    "collections",
+    "indexes",
+    "databases",
+    "functions",
+    "keys",
+    "tokens",
+    "credentials",
+    "roles",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 /// Denotes a resource ref.
+///
+/// A ref has an id and usually a class, which itself is a ref. Refs to the
+/// classes Fauna provides, such as `classes`, `indexes` or `keys`, have no
+/// class and are called native refs. Refs in a child database carry the
+/// database as their scope.
+///
+/// When reading a ref from Fauna, the class can be given as `class`,
+/// `collection` or `index`. Refs are always written with `class`.
+#[serde(rename = "$faunadb::Ref")]
 pub struct Ref<'a> {
     pub id: Cow<'a, str>,
-    #[serde(skip_serializing_if = "Option::is_none", flatten)]
-    location: Option<RefLocation<'a>>,
+    #[serde(
+        rename = "class",
+        alias = "collection",
+        alias = "index",
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_nested",
+        deserialize_with = "deserialize_nested"
+    )]
+    class: Option<Box<Ref<'a>>>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_nested",
+        deserialize_with = "deserialize_nested"
+    )]
+    database: Option<Box<Ref<'a>>>,
+}
+
+/// A ref inside a ref, wrapped in the `@ref` annotation.
+#[derive(Serialize, Deserialize)]
+struct Nested<T> {
+    #[serde(rename = "@ref")]
+    inner: T,
+}
+
+fn serialize_nested<S>(reference: &Option<Box<Ref>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match reference {
+        Some(inner) => Nested { inner }.serialize(serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_nested<'de, 'a, D>(deserializer: D) -> Result<Option<Box<Ref<'a>>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let nested: Nested<Ref<'a>> = Nested::deserialize(deserializer)?;
+    Ok(Some(Box::new(nested.inner)))
+}
+
+/// Refs are ordered by their database first, then by their class and id.
+impl<'a> Ord for Ref<'a> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.database
+            .cmp(&other.database)
+            .then_with(|| self.class.cmp(&other.class))
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+impl<'a> PartialOrd for Ref<'a> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl<'a> fmt::Display for Ref<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self.location {
-            Some(RefLocation::Class { ref location }) => {
-                write!(f, "Ref(id={},class={})", self.id, location.path())
-            }
-            Some(RefLocation::Index { ref location }) => {
-                write!(f, "Ref(id={},index={})", self.id, location.path())
-            }
-            Some(RefLocation::Function { ref location }) => {
-                write!(f, "Ref(id={},class={})", self.id, location.path())
-            }
-            Some(RefLocation::Database { ref location }) => {
-                write!(f, "Ref(id={},database={})", self.id, location.path())
+        write!(f, "Ref(id={}", self.id)?;
+
+        if let Some(ref class) = self.class {
+            match &*class.id {
+                "indexes" if class.class.is_none() => write!(f, ",index={}", class.path())?,
+                "databases" if class.class.is_none() => write!(f, ",database={}", class.path())?,
+                _ => write!(f, ",class={}", class.path())?,
             }
-            None => write!(f, "Ref(id={})", self.id),
         }
+
+        if let Some(ref database) = self.database {
+            write!(f, ",scope={}", database.path())?;
+        }
+
+        write!(f, ")")
     }
 }
 
@@ -71,34 +120,37 @@ impl<'a> Ref<'a> {
     {
         Self {
             id: id.into(),
-            location: None,
+            class: None,
+            database: None,
         }
     }
 
-    /// A ref to a class.
-    pub fn class<S>(id: S) -> Self
+    /// A ref in one of the native classes, such as `keys`.
+    fn in_native<S>(native: &'static str, id: S) -> Self
     where
         S: Into<Cow<'a, str>>,
     {
         Self {
             id: id.into(),
-            location: Some(RefLocation::Class {
-                location: Box::new(Self::instance("classes")),
-            }),
+            class: Some(Box::new(Self::instance(native))),
+            database: None,
         }
     }
 
+    /// A ref to a class.
+    pub fn class<S>(id: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        Self::in_native("classes", id)
+    }
+
     /// A ref to an index.
     pub fn index<S>(id: S) -> Self
     where
         S: Into<Cow<'a, str>>,
     {
-        Self {
-            id: id.into(),
-            location: Some(RefLocation::Index {
-                location: Box::new(Self::instance("indexes")),
-            }),
-        }
+        Self::in_native("indexes", id)
     }
 
     /// A ref to a function.
@@ -106,12 +158,7 @@ impl<'a> Ref<'a> {
     where
         S: Into<Cow<'a, str>>,
     {
-        Self {
-            id: id.into(),
-            location: Some(RefLocation::Function {
-                location: Box::new(Self::instance("functions")),
-            }),
-        }
+        Self::in_native("functions", id)
     }
 
     /// A ref to a database.
@@ -119,12 +166,39 @@ impl<'a> Ref<'a> {
     where
         S: Into<Cow<'a, str>>,
     {
-        Self {
-            id: id.into(),
-            location: Some(RefLocation::Database {
-                location: Box::new(Self::instance("databases")),
-            }),
-        }
+        Self::in_native("databases", id)
+    }
+
+    /// A ref to a key.
+    pub fn key<S>(id: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        Self::in_native("keys", id)
+    }
+
+    /// A ref to a token.
+    pub fn token<S>(id: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        Self::in_native("tokens", id)
+    }
+
+    /// A ref to credentials.
+    pub fn credential<S>(id: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        Self::in_native("credentials", id)
+    }
+
+    /// A ref to a role.
+    pub fn role<S>(id: S) -> Self
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        Self::in_native("roles", id)
     }
 
     /// Set the class for the singleton ref.
@@ -132,10 +206,7 @@ impl<'a> Ref<'a> {
     where
         S: Into<Cow<'a, str>>,
     {
-        self.location = Some(RefLocation::Class {
-            location: Box::new(Self::class(id)),
-        });
-
+        self.class = Some(Box::new(Self::class(id)));
         self
     }
 
@@ -144,18 +215,305 @@ impl<'a> Ref<'a> {
     where
         S: Into<Cow<'a, str>>,
     {
-        self.location = Some(RefLocation::Index {
-            location: Box::new(Self::index(id)),
-        });
+        self.class = Some(Box::new(Self::index(id)));
+        self
+    }
+
+    /// Scope the ref to a child database. The scope is set where Fauna puts
+    /// it: to the ref of the class for instances, and to the ref itself for
+    /// schema and native refs.
+    pub fn set_database_scope(&mut self, database: Ref<'a>) -> &mut Self {
+        match self.class {
+            Some(ref mut class) if !class.is_native() => {
+                class.set_database_scope(database);
+            }
+            _ => self.database = Some(Box::new(database)),
+        }
 
         self
     }
 
-    /// Gets the fully qualified path.
+    /// The class of the ref, `None` for native refs and refs with no class.
+    pub fn collection(&self) -> Option<&Ref<'a>> {
+        self.class.as_deref()
+    }
+
+    /// The child database the ref is scoped to, if any, on the ref itself or
+    /// on its class.
+    pub fn database_scope(&self) -> Option<&Ref<'a>> {
+        match self.database {
+            Some(ref database) => Some(database),
+            None => self.class.as_ref().and_then(|class| class.database_scope()),
+        }
+    }
+
+    /// `true` for the refs of Fauna's own classes, such as `classes`, `keys`
+    /// or `tokens`.
+    pub fn is_native(&self) -> bool {
+        self.class.is_none() && NATIVE_IDS.contains(&&*self.id)
+    }
+
+    /// The database scope set on this ref, not on its class.
+    pub(crate) fn own_database(&self) -> Option<&Ref<'a>> {
+        self.database.as_deref()
+    }
+
+    /// Gets the fully qualified path, such as `classes/users/123`. Refs in a
+    /// child database are prefixed with the path of the database.
     pub fn path(&self) -> String {
-        match self.location {
-            Some(ref location) => format!("{}/{}", location.path(), self.id),
+        let path = match self.class {
+            Some(ref class) => format!("{}/{}", class.path(), self.id),
             None => format!("{}", self.id),
+        };
+
+        match self.database {
+            Some(ref database) => format!("{}/{}", database.path(), path),
+            None => path,
+        }
+    }
+}
+
+/// An error from parsing a ref path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRefError(&'static str);
+
+impl fmt::Display for ParseRefError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseRefError {}
+
+/// Parses a path, the inverse of [path](struct.Ref.html#method.path).
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// #
+/// let mut user = Ref::instance("123");
+/// user.set_class("users");
+///
+/// assert_eq!(user, "classes/users/123".parse().unwrap());
+///
+/// let mut scoped = Ref::class("users");
+/// scoped.set_database_scope(Ref::database("child"));
+///
+/// assert_eq!("databases/child/classes/users", scoped.path());
+/// assert_eq!(scoped, scoped.path().parse().unwrap());
+/// ```
+impl FromStr for Ref<'static> {
+    type Err = ParseRefError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut current: Option<Ref<'static>> = None;
+
+        for segment in path.split('/') {
+            if segment.is_empty() {
+                return Err(ParseRefError("Empty segment in a ref path"));
+            }
+
+            let segment = segment.to_string();
+
+            current = Some(match current {
+                None => Ref::instance(segment),
+                // A native ref after a database starts a scope in it.
+                Some(database)
+                    if NATIVE_IDS.contains(&segment.as_str())
+                        && database
+                            .collection()
+                            .map(|class| class.is_native() && class.id == "databases")
+                            .unwrap_or(false) =>
+                {
+                    let mut native = Ref::instance(segment);
+                    native.database = Some(Box::new(database));
+                    native
+                }
+                // Fauna keeps the scope on the class, not on the native ref.
+                Some(mut parent) => {
+                    let database = if parent.is_native() {
+                        parent.database.take()
+                    } else {
+                        None
+                    };
+
+                    Ref {
+                        id: Cow::from(segment),
+                        class: Some(Box::new(parent)),
+                        database,
+                    }
+                }
+            });
+        }
+
+        current.ok_or(ParseRefError("Empty ref path"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    fn read(json: serde_json::Value) -> Ref<'static> {
+        match serde_json::from_value(json).unwrap() {
+            Value::Annotated(AnnotatedValue::Ref(reference)) => reference,
+            other => panic!("expected a ref, got {:?}", other),
+        }
+    }
+
+    fn round_trip(json: serde_json::Value) {
+        let reference = read(json.clone());
+        let serialized = serde_json::to_value(&Expr::from(reference)).unwrap();
+
+        assert_eq!(json, serialized);
+    }
+
+    #[test]
+    fn test_schema_refs() {
+        let classes = json!({ "@ref": { "id": "classes" } });
+
+        for (native, reference) in &[
+            ("classes", Ref::class("users")),
+            ("indexes", Ref::index("all_users")),
+            ("functions", Ref::function("double")),
+            ("databases", Ref::database("child")),
+            ("keys", Ref::key("1")),
+            ("tokens", Ref::token("2")),
+            ("credentials", Ref::credential("3")),
+            ("roles", Ref::role("admin")),
+        ] {
+            let serialized = serde_json::to_value(&Expr::from(reference.clone())).unwrap();
+
+            assert_eq!(
+                json!({ "@ref": { "class": { "@ref": { "id": native } }, "id": reference.id } }),
+                serialized
+            );
+
+            assert!(reference.collection().unwrap().is_native());
+            assert!(!reference.is_native());
         }
+
+        assert_eq!(Ref::instance("classes"), read(classes));
+        assert!(Ref::instance("classes").is_native());
+        assert!(!Ref::instance("musti").is_native());
+    }
+
+    #[test]
+    fn test_round_trip_fauna_shapes() {
+        round_trip(json!({ "@ref": { "id": "keys" } }));
+
+        round_trip(json!({
+            "@ref": {
+                "class": {
+                    "@ref": {
+                        "class": { "@ref": { "id": "classes" } },
+                        "id": "users"
+                    }
+                },
+                "id": "123"
+            }
+        }));
+
+        round_trip(json!({
+            "@ref": {
+                "class": {
+                    "@ref": {
+                        "class": { "@ref": { "id": "classes" } },
+                        "database": {
+                            "@ref": {
+                                "class": { "@ref": { "id": "databases" } },
+                                "id": "child"
+                            }
+                        },
+                        "id": "users"
+                    }
+                },
+                "id": "123"
+            }
+        }));
+
+        round_trip(json!({
+            "@ref": {
+                "database": {
+                    "@ref": {
+                        "class": { "@ref": { "id": "databases" } },
+                        "id": "child"
+                    }
+                },
+                "id": "classes"
+            }
+        }));
+    }
+
+    #[test]
+    fn test_read_aliases() {
+        let collection = read(json!({
+            "@ref": {
+                "collection": {
+                    "@ref": {
+                        "collection": { "@ref": { "id": "collections" } },
+                        "id": "users"
+                    }
+                },
+                "id": "123"
+            }
+        }));
+
+        assert_eq!("collections/users/123", collection.path());
+
+        let index = read(json!({
+            "@ref": { "index": { "@ref": { "id": "indexes" } }, "id": "all_users" }
+        }));
+
+        assert_eq!(Ref::index("all_users"), index);
+    }
+
+    #[test]
+    fn test_database_scope() {
+        let mut user = Ref::instance("123");
+        user.set_class("users");
+        user.set_database_scope(Ref::database("child"));
+
+        assert_eq!(Some(&Ref::database("child")), user.database_scope());
+        assert_eq!(None, user.own_database());
+        assert_eq!(
+            Some(&Ref::database("child")),
+            user.collection().unwrap().own_database()
+        );
+
+        let mut classes = Ref::instance("classes");
+        classes.set_database_scope(Ref::database("child"));
+
+        assert_eq!(Some(&Ref::database("child")), classes.own_database());
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let mut user = Ref::instance("123");
+        user.set_class("users");
+
+        assert_eq!("classes/users/123", user.path());
+        assert_eq!(user, "classes/users/123".parse().unwrap());
+        assert_eq!(
+            Ref::index("all_users"),
+            "indexes/all_users".parse().unwrap()
+        );
+        assert_eq!(Ref::instance("musti"), "musti".parse().unwrap());
+
+        user.set_database_scope(Ref::database("child"));
+
+        let path = user.path();
+        assert_eq!("databases/child/classes/users/123", path);
+        assert_eq!(user, path.parse().unwrap());
+
+        let mut nested = Ref::database("grandchild");
+        nested.set_database_scope(Ref::database("child"));
+
+        let path = nested.path();
+        assert_eq!("databases/child/databases/grandchild", path);
+        assert_eq!(nested, path.parse().unwrap());
+
+        assert!("".parse::<Ref>().is_err());
+        assert!("classes//123".parse::<Ref>().is_err());
     }
 }
@@ -1,11 +1,22 @@
-use crate::expr::{Expr, Ref};
-use std::fmt;
-
-#[derive(Debug, Serialize, Clone)]
-pub struct Set<'a> {
-    #[serde(rename = "match")]
-    matching: Expr<'a>,
-    terms: Expr<'a>,
+use crate::{
+    expr::{Expr, IntoOwned, Ref},
+    fql,
+};
+use std::{collections::BTreeMap, fmt};
+
+/// A set identifier, written with the `@set` annotation.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Hash)]
+pub struct Set<'a>(SetExpr<'a>);
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+enum SetExpr<'a> {
+    Match {
+        #[serde(rename = "match")]
+        matching: Expr<'a>,
+        terms: Expr<'a>,
+    },
+    Raw(Expr<'a>),
 }
 
 impl<'a> Set<'a> {
@@ -16,12 +27,35 @@ impl<'a> Set<'a> {
         let matching = Expr::from(reference);
         let terms = terms.into();
 
-        Self { matching, terms }
+        Set(SetExpr::Match { matching, terms })
+    }
+
+    /// A set from its contents as returned by Fauna, such as a union or a
+    /// join, written back as-is.
+    pub(crate) fn raw(expr: Expr<'a>) -> Self {
+        Set(SetExpr::Raw(expr))
+    }
+}
+
+impl<'a> IntoOwned for Set<'a> {
+    type Owned = Set<'static>;
+
+    fn into_owned(self) -> Set<'static> {
+        match self.0 {
+            SetExpr::Match { matching, terms } => Set(SetExpr::Match {
+                matching: matching.into_owned(),
+                terms: terms.into_owned(),
+            }),
+            SetExpr::Raw(expr) => Set(SetExpr::Raw(expr.into_owned())),
+        }
     }
 }
 
 impl<'a> fmt::Display for Set<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Set(match={},terms={})", self.matching, self.terms)
+        let mut annotated = BTreeMap::new();
+        annotated.insert("@set", self);
+
+        fql::write_query(&annotated, f)
     }
 }
@@ -0,0 +1,518 @@
+//! Checking an expression for mistakes before sending it to Fauna.
+use super::Expr;
+use serde_json::{Map, Value as Json};
+use std::fmt;
+
+/// The largest page `Paginate` can return.
+pub const MAX_PAGE_SIZE: u64 = 100_000;
+
+/// The default limit for the size of the serialized query, in bytes.
+pub const DEFAULT_MAX_PAYLOAD_SIZE: usize = 8 * 1024 * 1024;
+
+/// A mistake found in an expression, and where in the query it is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    path: String,
+    problem: Problem,
+}
+
+/// The kinds of mistakes the validation can find.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// A `Var` with no `Lambda` or `Let` binding the name.
+    UnboundVar(String),
+    /// A lambda mapping over arrays of a different length than its
+    /// parameters.
+    LambdaArity { params: usize, items: usize },
+    /// A user-defined function body that is not a quoted `Lambda`, and would
+    /// be evaluated when creating the function.
+    UnquotedBody,
+    /// A page larger than the server allows.
+    PageSize(u64),
+    /// A serialized query larger than the configured limit.
+    PayloadSize { size: usize, max: usize },
+}
+
+impl Finding {
+    /// The location of the mistake in the wire format of the query, e.g.
+    /// `$.map.expr.add[1]`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn problem(&self) -> &Problem {
+        &self.problem
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.problem)
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::UnboundVar(name) => write!(f, "variable `{}` is not bound", name),
+            Problem::LambdaArity { params, items } => write!(
+                f,
+                "lambda takes {} parameters, but the items have {} elements",
+                params, items
+            ),
+            Problem::UnquotedBody => write!(f, "function body is not quoted"),
+            Problem::PageSize(size) => write!(
+                f,
+                "page size {} is over the maximum of {}",
+                size, MAX_PAGE_SIZE
+            ),
+            Problem::PayloadSize { size, max } => {
+                write!(f, "query is {} bytes, over the maximum of {}", size, max)
+            }
+        }
+    }
+}
+
+/// Validates expressions with configurable limits. `Expr::validate` uses the
+/// defaults.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// # use faunadb::expr::Validator;
+/// #
+/// let query = Paginate::new(Match::new(Index::find("all_cats")));
+///
+/// let mut validator = Validator::default();
+/// validator.max_payload_size(16);
+///
+/// let findings = validator.validate(&Expr::from(query));
+///
+/// assert_eq!(
+///     "$: query is 84 bytes, over the maximum of 16",
+///     findings[0].to_string()
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Validator {
+    max_payload_size: usize,
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Self {
+            max_payload_size: DEFAULT_MAX_PAYLOAD_SIZE,
+        }
+    }
+}
+
+impl Validator {
+    /// The largest allowed serialized query, in bytes.
+    pub fn max_payload_size(&mut self, bytes: usize) -> &mut Self {
+        self.max_payload_size = bytes;
+        self
+    }
+
+    /// All findings in the expression, as a complete query. Empty if nothing
+    /// was found.
+    pub fn validate(&self, expr: &Expr) -> Vec<Finding> {
+        let mut walker = Walker::default();
+
+        let json = match serde_json::to_value(expr) {
+            Ok(json) => json,
+            Err(_) => return walker.findings,
+        };
+
+        let size = json.to_string().len();
+
+        if size > self.max_payload_size {
+            walker.report(
+                "$",
+                Problem::PayloadSize {
+                    size,
+                    max: self.max_payload_size,
+                },
+            );
+        }
+
+        walker.walk(&json, "$");
+        walker.findings
+    }
+}
+
+impl<'a> Expr<'a> {
+    /// Checks the expression for common mistakes before sending it: unbound
+    /// variables, lambda arity, unquoted function bodies, page sizes and the
+    /// payload size, with the default limits of [Validator](struct.Validator.html).
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// #
+    /// let query = Map::new(
+    ///     Array::from(vec![Array::from(vec![1, 2])]),
+    ///     Lambda::new("x", Add::new(Array::from(vec![Var::new("x"), Var::new("y")]))),
+    /// );
+    ///
+    /// let findings: Vec<String> = Expr::from(query)
+    ///     .validate()
+    ///     .iter()
+    ///     .map(|finding| finding.to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(vec!["$.map.expr.add[1]: variable `y` is not bound"], findings);
+    /// ```
+    pub fn validate(&self) -> Vec<Finding> {
+        Validator::default().validate(self)
+    }
+}
+
+#[derive(Default)]
+struct Walker {
+    scope: Vec<String>,
+    findings: Vec<Finding>,
+}
+
+impl Walker {
+    fn report(&mut self, path: &str, problem: Problem) {
+        self.findings.push(Finding {
+            path: path.to_string(),
+            problem,
+        });
+    }
+
+    fn walk(&mut self, json: &Json, path: &str) {
+        match json {
+            Json::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.walk(item, &format!("{}[{}]", path, i));
+                }
+            }
+            Json::Object(obj) => self.walk_object(obj, path),
+            _ => (),
+        }
+    }
+
+    fn walk_object(&mut self, obj: &Map<String, Json>, path: &str) {
+        if let Some(quoted) = obj.get("@query") {
+            return self.walk(quoted, &format!("{}.@query", path));
+        }
+
+        if obj.keys().any(|key| key.starts_with('@')) {
+            return;
+        }
+
+        if let (Some(fields), 1) = (obj.get("object"), obj.len()) {
+            if let Json::Object(fields) = fields {
+                for (key, value) in fields {
+                    self.walk(value, &format!("{}.object.{}", path, key));
+                }
+            }
+
+            return;
+        }
+
+        if let (Some(Json::String(name)), 1) = (obj.get("var"), obj.len()) {
+            if !self.scope.contains(name) {
+                self.report(path, Problem::UnboundVar(name.clone()));
+            }
+
+            return;
+        }
+
+        if let Some(params) = obj.get("lambda") {
+            return self.walk_lambda(obj, params, path);
+        }
+
+        if let Some(bindings) = obj.get("let") {
+            return self.walk_let(obj, bindings, path);
+        }
+
+        self.check_call(obj, path);
+
+        for (key, value) in obj {
+            self.walk(value, &format!("{}.{}", path, key));
+        }
+    }
+
+    fn walk_lambda(&mut self, obj: &Map<String, Json>, params: &Json, path: &str) {
+        let names = lambda_params(params);
+        let depth = self.scope.len();
+
+        self.scope
+            .extend(names.into_iter().filter(|name| name != "_"));
+
+        if let Some(expr) = obj.get("expr") {
+            self.walk(expr, &format!("{}.expr", path));
+        }
+
+        self.scope.truncate(depth);
+    }
+
+    /// Bindings are evaluated in order, each seeing the ones before it.
+    fn walk_let(&mut self, obj: &Map<String, Json>, bindings: &Json, path: &str) {
+        let depth = self.scope.len();
+
+        let bindings: Vec<(String, &Json, String)> = match bindings {
+            Json::Object(bindings) => bindings
+                .iter()
+                .map(|(name, value)| (name.clone(), value, format!("{}.let.{}", path, name)))
+                .collect(),
+            Json::Array(bindings) => bindings
+                .iter()
+                .enumerate()
+                .filter_map(|(i, binding)| match binding {
+                    Json::Object(binding) => Some((i, binding)),
+                    _ => None,
+                })
+                .flat_map(|(i, binding)| {
+                    binding.iter().map(move |(name, value)| {
+                        (name.clone(), value, format!("{}.let[{}].{}", path, i, name))
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        for (name, value, path) in bindings {
+            self.walk(value, &path);
+            self.scope.push(name);
+        }
+
+        if let Some(in_expr) = obj.get("in") {
+            self.walk(in_expr, &format!("{}.in", path));
+        }
+
+        self.scope.truncate(depth);
+    }
+
+    fn check_call(&mut self, obj: &Map<String, Json>, path: &str) {
+        if let (Some(_), Some(size)) = (obj.get("paginate"), obj.get("size")) {
+            if let Some(size) = size.as_u64().filter(|size| *size > MAX_PAGE_SIZE) {
+                self.report(&format!("{}.size", path), Problem::PageSize(size));
+            }
+        }
+
+        for key in &["map", "foreach", "filter"] {
+            if let (Some(lambda), Some(collection)) = (obj.get(*key), obj.get("collection")) {
+                self.check_arity(lambda, collection, &format!("{}.{}", path, key));
+            }
+        }
+
+        if let Some(params) = obj.get("create_function") {
+            self.check_body(params, &format!("{}.create_function", path));
+        }
+
+        for key in &["update", "replace"] {
+            if let (Some(reference), Some(params)) = (obj.get(*key), obj.get("params")) {
+                if is_function_ref(reference) {
+                    self.check_body(params, &format!("{}.params", path));
+                }
+            }
+        }
+    }
+
+    fn check_arity(&mut self, lambda: &Json, collection: &Json, path: &str) {
+        let params = match lambda.get("lambda") {
+            Some(Json::Array(params)) => params.len(),
+            Some(Json::String(_)) => 1,
+            _ => return,
+        };
+
+        let items = match collection {
+            Json::Array(items) => items,
+            _ => return,
+        };
+
+        for item in items {
+            let len = match item {
+                Json::Array(elements) => elements.len(),
+                _ => 1,
+            };
+
+            // A single parameter takes the whole item.
+            if params > 1 && len != params {
+                return self.report(
+                    &format!("{}.lambda", path),
+                    Problem::LambdaArity { params, items: len },
+                );
+            }
+        }
+    }
+
+    fn check_body(&mut self, params: &Json, path: &str) {
+        let body = params.get("object").and_then(|object| object.get("body"));
+
+        if let Some(body) = body {
+            if body.get("@query").is_none() {
+                self.report(&format!("{}.object.body", path), Problem::UnquotedBody);
+            }
+        }
+    }
+}
+
+fn lambda_params(params: &Json) -> Vec<String> {
+    match params {
+        Json::String(name) => vec![name.clone()],
+        Json::Array(names) => names
+            .iter()
+            .filter_map(|name| name.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn is_function_ref(reference: &Json) -> bool {
+    reference
+        .get("@ref")
+        .and_then(|reference| reference.get("class"))
+        .and_then(|class| class.get("@ref"))
+        .and_then(|class| class.get("id"))
+        .map(|id| id == "functions")
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Finding, Problem, Validator};
+    use crate::prelude::*;
+    use serde_json::{self, json};
+
+    fn problems(findings: Vec<Finding>) -> Vec<(String, Problem)> {
+        findings
+            .into_iter()
+            .map(|finding| (finding.path().to_string(), finding.problem().clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_query() {
+        let query = Map::new(
+            Paginate::new(Match::new(Index::find("all_cats"))),
+            Lambda::with(|cat| Let::with(Get::instance(cat), |data| data.select(vec!["data"]))),
+        );
+
+        assert!(Expr::from(query).validate().is_empty());
+    }
+
+    #[test]
+    fn test_unbound_vars() {
+        let query = Let::bindings(
+            vec![
+                Binding::new("a", Var::new("b")),
+                Binding::new("b", Var::new("a")),
+            ],
+            Array::from(vec![Var::new("a"), Var::new("c")]),
+        );
+
+        assert_eq!(
+            vec![
+                (
+                    String::from("$.let.a"),
+                    Problem::UnboundVar(String::from("b"))
+                ),
+                (
+                    String::from("$.in[1]"),
+                    Problem::UnboundVar(String::from("c"))
+                ),
+            ],
+            problems(Expr::from(query).validate())
+        );
+
+        let query = Array::from(vec![
+            Expr::from(Lambda::new("x", Var::new("x"))),
+            Expr::from(Var::new("x")),
+        ]);
+
+        assert_eq!(
+            vec![(String::from("$[1]"), Problem::UnboundVar(String::from("x")))],
+            problems(Expr::from(query).validate())
+        );
+    }
+
+    #[test]
+    fn test_lambda_arity() {
+        let pairs = Array::from(vec![Array::from(vec![1, 2]), Array::from(vec![3, 4])]);
+
+        let query = Map::new(
+            pairs.clone(),
+            Lambda::new(
+                Array::from(vec!["a", "b", "c"]),
+                Array::from(vec![Var::new("a"), Var::new("b"), Var::new("c")]),
+            ),
+        );
+
+        assert_eq!(
+            vec![(
+                String::from("$.map.lambda"),
+                Problem::LambdaArity {
+                    params: 3,
+                    items: 2
+                }
+            )],
+            problems(Expr::from(query).validate())
+        );
+
+        let query = Map::new(pairs, Lambda::with(|pair| pair));
+        assert!(Expr::from(query).validate().is_empty());
+    }
+
+    #[test]
+    fn test_unquoted_body() {
+        let query: Expr = serde_json::from_value(json!({
+            "create_function": {
+                "object": { "name": "double", "body": { "lambda": "x", "expr": { "var": "x" } } }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            vec![(
+                String::from("$.create_function.object.body"),
+                Problem::UnquotedBody
+            )],
+            problems(query.validate())
+        );
+
+        let query: Expr = serde_json::from_value(json!({
+            "replace": { "@ref": { "class": { "@ref": { "id": "functions" } }, "id": "double" } },
+            "params": { "object": { "body": { "lambda": "x", "expr": { "var": "x" } } } }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            vec![(String::from("$.params.object.body"), Problem::UnquotedBody)],
+            problems(query.validate())
+        );
+
+        let params = FunctionParams::new("double", Lambda::new("x", Var::new("x")));
+        assert!(Expr::from(CreateFunction::new(params))
+            .validate()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_page_and_payload_size() {
+        let mut paginate = Paginate::new(Match::new(Index::find("all_cats")));
+        paginate.size(100_001);
+
+        let query = Expr::from(paginate);
+
+        assert_eq!(
+            vec![(String::from("$.size"), Problem::PageSize(100_001))],
+            problems(query.validate())
+        );
+
+        let mut validator = Validator::default();
+        validator.max_payload_size(64);
+
+        assert_eq!(
+            vec![
+                (
+                    String::from("$"),
+                    Problem::PayloadSize { size: 88, max: 64 }
+                ),
+                (String::from("$.size"), Problem::PageSize(100_001)),
+            ],
+            problems(validator.validate(&query))
+        );
+    }
+}
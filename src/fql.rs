@@ -0,0 +1,926 @@
+//! Rendering values in the FQL notation of the Fauna shell, and
+//! [parsing](fn.parse.html) FQL text into expressions.
+//!
+//! `Value` implements `Display` with the default [Printer](struct.Printer.html):
+//! `{}` renders the value on one line, `{:#}` breaks it to lines of at most 80
+//! characters, indented with two spaces.
+//!
+//! ```
+//! # use faunadb::prelude::*;
+//! # use serde_json::json;
+//! #
+//! let value: Value = serde_json::from_value(json!({
+//!     "ref": {
+//!         "@ref": {
+//!             "class": {
+//!                 "@ref": { "class": { "@ref": { "id": "classes" } }, "id": "users" }
+//!             },
+//!             "id": "123"
+//!         }
+//!     },
+//!     "born": { "@date": "2011-07-07" },
+//! })).unwrap();
+//!
+//! assert_eq!(
+//!     "{ born: Date(\"2011-07-07\"), ref: Ref(Class(\"users\"), \"123\") }",
+//!     value.to_string(),
+//! );
+//! ```
+mod parse;
+
+pub use parse::parse;
+
+use crate::{
+    client::{AnnotatedValue, SetRef, SimpleValue, Value},
+    expr::{Expr, Ref},
+    query::Query,
+};
+use chrono::SecondsFormat;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt};
+
+/// A configurable printer for rendering values in FQL.
+///
+/// ```
+/// # use faunadb::{prelude::*, fql::Printer};
+/// #
+/// let value = Value::from(vec!["Musti", "Naukio"]);
+///
+/// let mut printer = Printer::new();
+/// printer.width(10).indent(4);
+///
+/// assert_eq!("[\n    \"Musti\",\n    \"Naukio\"\n]", printer.print(&value));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Printer {
+    indent: usize,
+    width: usize,
+}
+
+impl Default for Printer {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            width: 80,
+        }
+    }
+}
+
+impl Printer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of spaces for each level of nesting.
+    pub fn indent(&mut self, indent: usize) -> &mut Self {
+        self.indent = indent;
+        self
+    }
+
+    /// The maximum length of a line. Nodes longer than this are broken to
+    /// several lines. Strings and other atoms are never broken.
+    pub fn width(&mut self, width: usize) -> &mut Self {
+        self.width = width;
+        self
+    }
+
+    /// Renders the value.
+    pub fn print(&self, value: &Value) -> String {
+        let mut out = String::new();
+        self.write(&Node::from_value(value), 0, 0, &mut out);
+
+        out
+    }
+
+    pub(crate) fn print_node(&self, node: &Node) -> String {
+        let mut out = String::new();
+        self.write(node, 0, 0, &mut out);
+
+        out
+    }
+
+    fn write(&self, node: &Node, level: usize, column: usize, out: &mut String) {
+        let flat = node.flat();
+
+        if column + flat.len() <= self.width {
+            out.push_str(&flat);
+            return;
+        }
+
+        let inner = " ".repeat((level + 1) * self.indent);
+        let outer = " ".repeat(level * self.indent);
+
+        match node {
+            Node::Atom(atom) => out.push_str(atom),
+            Node::Call(_, args) if args.is_empty() => out.push_str(&flat),
+            Node::Array(items) if items.is_empty() => out.push_str(&flat),
+            Node::Object(fields) if fields.is_empty() => out.push_str(&flat),
+            Node::Call(name, args) => {
+                out.push_str(name);
+                out.push_str("(\n");
+                self.write_items(args, level, &inner, out);
+                out.push_str(&outer);
+                out.push(')');
+            }
+            Node::Array(items) => {
+                out.push_str("[\n");
+                self.write_items(items, level, &inner, out);
+                out.push_str(&outer);
+                out.push(']');
+            }
+            Node::Object(fields) => {
+                out.push_str("{\n");
+
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    let key = render_key(key);
+
+                    out.push_str(&inner);
+                    out.push_str(&key);
+                    out.push_str(": ");
+                    self.write(value, level + 1, inner.len() + key.len() + 2, out);
+
+                    if i + 1 < fields.len() {
+                        out.push(',');
+                    }
+
+                    out.push('\n');
+                }
+
+                out.push_str(&outer);
+                out.push('}');
+            }
+        }
+    }
+
+    fn write_items(&self, items: &[Node], level: usize, inner: &str, out: &mut String) {
+        for (i, item) in items.iter().enumerate() {
+            out.push_str(inner);
+            self.write(item, level + 1, inner.len(), out);
+
+            if i + 1 < items.len() {
+                out.push(',');
+            }
+
+            out.push('\n');
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_node(&Node::from_value(self), f)
+    }
+}
+
+/// Renders the expression as a query, e.g. `Get(Ref(Class("cats"), "1"))`.
+/// `{}` renders the query on one line, `{:#}` breaks it to lines of at most 80
+/// characters.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// #
+/// let query = Map::new(
+///     Paginate::new(Classes::all()),
+///     Lambda::new("x", Get::instance(Var::new("x"))),
+/// );
+///
+/// assert_eq!(
+///     "Map(Paginate(Classes(), { size: 64, events: false, sources: false }), \
+///      Lambda(\"x\", Get(Var(\"x\"))))",
+///     Expr::from(query).to_string(),
+/// );
+/// ```
+impl<'a> fmt::Display for Expr<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_query(self, f)
+    }
+}
+
+impl<'a> fmt::Display for Query<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_query(self, f)
+    }
+}
+
+/// Writes an expression as FQL, reading it back from its wire format.
+pub(crate) fn write_query<T>(query: &T, f: &mut fmt::Formatter) -> fmt::Result
+where
+    T: Serialize + ?Sized,
+{
+    let value = serde_json::to_value(query)
+        .and_then(serde_json::from_value::<Value>)
+        .map_err(|_| fmt::Error)?;
+
+    write_node(&Node::from_query(&value), f)
+}
+
+pub(crate) fn write_node(node: &Node, f: &mut fmt::Formatter) -> fmt::Result {
+    if f.alternate() {
+        write!(f, "{}", Printer::default().print_node(node))
+    } else {
+        write!(f, "{}", node.flat())
+    }
+}
+
+/// A value or a query in FQL, before laying it out to lines.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    /// Literals, never broken.
+    Atom(String),
+    /// A function call with positional arguments.
+    Call(String, Vec<Node>),
+    Array(Vec<Node>),
+    Object(Vec<(String, Node)>),
+}
+
+impl Node {
+    fn call(name: &str, args: Vec<Node>) -> Self {
+        Node::Call(name.to_string(), args)
+    }
+
+    fn string(s: &str) -> Self {
+        Node::Atom(serde_json::to_string(s).unwrap())
+    }
+
+    /// The node on one line.
+    pub(crate) fn flat(&self) -> String {
+        match self {
+            Node::Atom(atom) => atom.clone(),
+            Node::Call(name, args) => {
+                let args: Vec<String> = args.iter().map(Node::flat).collect();
+                format!("{}({})", name, args.join(", "))
+            }
+            Node::Array(items) => {
+                let items: Vec<String> = items.iter().map(Node::flat).collect();
+                format!("[{}]", items.join(", "))
+            }
+            Node::Object(fields) if fields.is_empty() => String::from("{}"),
+            Node::Object(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", render_key(k), v.flat()))
+                    .collect();
+
+                format!("{{ {} }}", fields.join(", "))
+            }
+        }
+    }
+
+    /// Renders a value as data: objects are plain objects.
+    pub(crate) fn from_value(value: &Value) -> Self {
+        match value {
+            Value::Simple(SimpleValue::Array(items)) => {
+                Node::Array(items.iter().map(Node::from_value).collect())
+            }
+            Value::Simple(SimpleValue::Object(obj)) => Node::object(obj, Node::from_value),
+            value => Node::scalar(value),
+        }
+    }
+
+    /// Renders a value in the query format, e.g. the contents of a set or a
+    /// quoted query: objects are function calls, and object literals are
+    /// wrapped in an `object` key.
+    pub(crate) fn from_query(value: &Value) -> Self {
+        match value {
+            Value::Simple(SimpleValue::Array(items)) => {
+                Node::Array(items.iter().map(Node::from_query).collect())
+            }
+            Value::Simple(SimpleValue::Object(obj)) => Node::query_object(obj),
+            value => Node::scalar(value),
+        }
+    }
+
+    fn object<F>(obj: &BTreeMap<String, Value>, render: F) -> Self
+    where
+        F: Fn(&Value) -> Node,
+    {
+        Node::Object(obj.iter().map(|(k, v)| (k.clone(), render(v))).collect())
+    }
+
+    fn scalar(value: &Value) -> Self {
+        match value {
+            Value::Simple(SimpleValue::String(s)) => Node::string(s),
+            Value::Simple(SimpleValue::Number(n)) => Node::Atom(render_number(*n)),
+            Value::Simple(SimpleValue::Boolean(b)) => Node::Atom(b.to_string()),
+            Value::Simple(SimpleValue::Null) => Node::Atom(String::from("null")),
+            Value::Simple(SimpleValue::Array(_)) | Value::Simple(SimpleValue::Object(_)) => {
+                Node::from_value(value)
+            }
+            Value::Annotated(AnnotatedValue::Ref(reference)) => Node::reference(reference),
+            Value::Annotated(AnnotatedValue::Timestamp(ts)) => Node::call(
+                "Time",
+                vec![Node::string(
+                    &ts.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                )],
+            ),
+            Value::Annotated(AnnotatedValue::Date(date)) => {
+                Node::call("Date", vec![Node::string(&date.to_string())])
+            }
+            Value::Annotated(AnnotatedValue::Bytes(bytes)) => {
+                Node::call("Bytes", vec![Node::string(&base64::encode(&bytes.0))])
+            }
+            Value::Annotated(AnnotatedValue::Set(set)) => Node::set(set),
+            Value::Annotated(AnnotatedValue::Query(query)) => {
+                Node::call("Query", vec![Node::from_query(query)])
+            }
+        }
+    }
+
+    pub(crate) fn set(set: &SetRef) -> Self {
+        let sets = |sets: &[SetRef]| sets.iter().map(Node::set).collect();
+
+        match set {
+            SetRef::Match { index, terms } => {
+                let mut args = vec![Node::reference(index)];
+                args.extend(terms.as_ref().map(Node::from_query));
+
+                Node::call("Match", args)
+            }
+            SetRef::Union(union) => Node::call("Union", sets(union)),
+            SetRef::Intersection(intersection) => Node::call("Intersection", sets(intersection)),
+            SetRef::Difference(difference) => Node::call("Difference", sets(difference)),
+            SetRef::Distinct(set) => Node::call("Distinct", vec![Node::set(set)]),
+            SetRef::Join { source, target } => {
+                Node::call("Join", vec![Node::set(source), Node::from_query(target)])
+            }
+            SetRef::Other(value) => Node::from_query(value),
+        }
+    }
+
+    pub(crate) fn reference(reference: &Ref) -> Self {
+        let id = &*reference.id;
+        let scope = reference.own_database().map(Node::reference);
+
+        match reference.collection() {
+            None => match native_ref(id) {
+                Some(name) => Node::call(name, scope.into_iter().collect()),
+                None => Node::call("Ref", vec![Node::string(id)]),
+            },
+            Some(parent) if parent.is_native() => {
+                let scope = scope.or_else(|| parent.own_database().map(Node::reference));
+
+                match schema_ref(&parent.id) {
+                    Some(name) => {
+                        let mut args = vec![Node::string(id)];
+                        args.extend(scope);
+
+                        Node::call(name, args)
+                    }
+                    None => {
+                        let native = native_ref(&parent.id).unwrap_or("Ref");
+                        let parent = Node::call(native, scope.into_iter().collect());
+
+                        Node::call("Ref", vec![parent, Node::string(id)])
+                    }
+                }
+            }
+            Some(parent) => Node::call("Ref", vec![Node::reference(parent), Node::string(id)]),
+        }
+    }
+
+    fn query_object(obj: &BTreeMap<String, Value>) -> Self {
+        if obj.len() == 1 {
+            if let Some(Value::Simple(SimpleValue::Object(fields))) = obj.get("object") {
+                return Node::object(fields, Node::from_query);
+            }
+        }
+
+        match find_function(obj.keys().map(String::as_str)) {
+            Some(function) => function.render(obj),
+            None => Node::object(obj, Node::from_query),
+        }
+    }
+}
+
+/// An FQL function, mapping its wire format to a call.
+pub(crate) struct Function {
+    /// The key identifying the function.
+    pub(crate) key: &'static str,
+    /// The name of the function in FQL.
+    pub(crate) name: &'static str,
+    /// The keys of the positional arguments, including `key`, which is the
+    /// first one unless marked `key_last`. Missing arguments at the end are
+    /// left out.
+    pub(crate) args: &'static [&'static str],
+    /// Optional parameters, rendered as an object after the positional
+    /// arguments.
+    pub(crate) options: &'static [&'static str],
+    /// The first argument is an array of the actual arguments, e.g.
+    /// `Add(1, 2)` is `{"add": [1, 2]}`.
+    pub(crate) variadic: bool,
+}
+
+macro_rules! functions {
+    ($($key:literal => $name:literal ($($arg:literal),*) $({ $($opt:literal),* })? $(.. $flag:ident)?;)*) => {
+        pub(crate) const FUNCTIONS: &[Function] = &[
+            $(
+                Function {
+                    key: $key,
+                    name: $name,
+                    args: functions!(@args $key [$($arg),*] $($flag)?),
+                    options: &[$($($opt),*)?],
+                    variadic: functions!(@variadic $($flag)?),
+                },
+            )*
+        ];
+    };
+    (@args $key:literal [$($arg:literal),*] key_last) => { &[$($arg,)* $key] };
+    (@args $key:literal [$($arg:literal),*] $($flag:ident)?) => { &[$key $(, $arg)*] };
+    (@variadic variadic) => { true };
+    (@variadic $($flag:ident)?) => { false };
+}
+
+functions! {
+    "abort" => "Abort"();
+    "abs" => "Abs"();
+    "acos" => "Acos"();
+    "add" => "Add"() ..variadic;
+    "and" => "And"() ..variadic;
+    "append" => "Append"("collection");
+    "asin" => "Asin"();
+    "at" => "At"("expr");
+    "atan" => "Atan"();
+    "bitand" => "BitAnd"() ..variadic;
+    "bitnot" => "BitNot"();
+    "bitor" => "BitOr"() ..variadic;
+    "bitxor" => "BitXor"() ..variadic;
+    "call" => "Call"("arguments");
+    "casefold" => "Casefold"("normalizer");
+    "ceil" => "Ceil"();
+    "class" => "Class"("scope");
+    "classes" => "Classes"();
+    "concat" => "Concat"("separator");
+    "contains" => "Contains"("in");
+    "cos" => "Cos"();
+    "cosh" => "Cosh"();
+    "create" => "Create"("params");
+    "create_class" => "CreateClass"();
+    "create_database" => "CreateDatabase"();
+    "create_function" => "CreateFunction"();
+    "create_index" => "CreateIndex"();
+    "create_key" => "CreateKey"();
+    "database" => "Database"("scope");
+    "databases" => "Databases"();
+    "date" => "Date"();
+    "degrees" => "Degrees"();
+    "delete" => "Delete"();
+    "difference" => "Difference"() ..variadic;
+    "distinct" => "Distinct"();
+    "divide" => "Divide"() ..variadic;
+    "do" => "Do"() ..variadic;
+    "drop" => "Drop"("collection");
+    "epoch" => "Epoch"("unit");
+    "equals" => "Equals"() ..variadic;
+    "exists" => "Exists"("ts");
+    "exp" => "Exp"();
+    "filter" => "Filter"("collection") ..key_last;
+    "findstr" => "FindStr"("find", "start");
+    "findstrregex" => "FindStrRegex"("pattern", "start", "num_results");
+    "floor" => "Floor"();
+    "foreach" => "Foreach"("collection") ..key_last;
+    "function" => "Function"("scope");
+    "functions" => "Functions"();
+    "get" => "Get"("ts");
+    "gt" => "GT"() ..variadic;
+    "gte" => "GTE"() ..variadic;
+    "has_identity" => "HasIdentity"();
+    "hypot" => "Hypot"("b");
+    "identify" => "Identify"("password");
+    "identity" => "Identity"();
+    "if" => "If"("then", "else");
+    "index" => "Index"("scope");
+    "indexes" => "Indexes"();
+    "insert" => "Insert"("ts", "action", "params");
+    "intersection" => "Intersection"() ..variadic;
+    "is_empty" => "IsEmpty"();
+    "is_nonempty" => "IsNonEmpty"();
+    "join" => "Join"("with");
+    "key_from_secret" => "KeyFromSecret"();
+    "lambda" => "Lambda"("expr");
+    "length" => "Length"();
+    "let" => "Let"("in");
+    "ln" => "Ln"();
+    "log" => "Log"();
+    "login" => "Login"("params");
+    "logout" => "Logout"();
+    "lowercase" => "LowerCase"();
+    "ltrim" => "LTrim"();
+    "lt" => "LT"() ..variadic;
+    "lte" => "LTE"() ..variadic;
+    "map" => "Map"("collection") ..key_last;
+    "match" => "Match"("terms");
+    "max" => "Max"() ..variadic;
+    "min" => "Min"() ..variadic;
+    "modulo" => "Modulo"() ..variadic;
+    "multiply" => "Multiply"() ..variadic;
+    "new_id" => "NewId"();
+    "not" => "Not"();
+    "or" => "Or"() ..variadic;
+    "paginate" => "Paginate"() { "size", "after", "before", "ts", "events", "sources" };
+    "pow" => "Pow"("exp");
+    "prepend" => "Prepend"("collection");
+    "radians" => "Radians"();
+    "remove" => "Remove"("ts", "action");
+    "repeat" => "Repeat"("number");
+    "replace" => "Replace"("params");
+    "replacestr" => "ReplaceStr"("find", "replace");
+    "replacestrregex" => "ReplaceStrRegex"("pattern", "replace", "first");
+    "round" => "Round"("precision");
+    "rtrim" => "RTrim"();
+    "select" => "Select"("from", "default");
+    "select_all" => "SelectAll"("from");
+    "sign" => "Sign"();
+    "sin" => "Sin"();
+    "sinh" => "Sinh"();
+    "space" => "Space"();
+    "sqrt" => "Sqrt"();
+    "substring" => "SubString"("start", "length");
+    "subtract" => "Subtract"() ..variadic;
+    "take" => "Take"("collection");
+    "tan" => "Tan"();
+    "tanh" => "Tanh"();
+    "time" => "Time"();
+    "titlecase" => "TitleCase"();
+    "to_date" => "ToDate"();
+    "to_number" => "ToNumber"();
+    "to_string" => "ToString"();
+    "to_time" => "ToTime"();
+    "trim" => "Trim"();
+    "trunc" => "Trunc"("precision");
+    "union" => "Union"() ..variadic;
+    "update" => "Update"("params");
+    "uppercase" => "UpperCase"();
+    "var" => "Var"();
+}
+
+/// Finds the function with the given keys in its wire format.
+pub(crate) fn find_function<'a, I>(keys: I) -> Option<&'static Function>
+where
+    I: Iterator<Item = &'a str> + Clone,
+{
+    FUNCTIONS.iter().find(|function| {
+        keys.clone().any(|key| key == function.key)
+            && keys
+                .clone()
+                .all(|key| function.args.contains(&key) || function.options.contains(&key))
+    })
+}
+
+impl Function {
+    fn render(&self, obj: &BTreeMap<String, Value>) -> Node {
+        let mut args: Vec<Node> = Vec::new();
+
+        let last = self
+            .args
+            .iter()
+            .rposition(|key| obj.contains_key(*key))
+            .unwrap_or(0);
+
+        for key in &self.args[..=last] {
+            match obj.get(*key) {
+                Some(Value::Simple(SimpleValue::Array(items)))
+                    if self.variadic && *key == self.key =>
+                {
+                    args.extend(items.iter().map(Node::from_query));
+                }
+                Some(Value::Simple(SimpleValue::Object(bindings)))
+                    if self.key == "let" && *key == "let" =>
+                {
+                    args.push(Node::object(bindings, Node::from_query));
+                }
+                Some(value) => args.push(Node::from_query(value)),
+                None => args.push(Node::Atom(String::from("null"))),
+            }
+        }
+
+        // Scopes of the schema functions, such as `Classes()`, are optional.
+        let scoped = ["classes", "indexes", "databases", "functions"].contains(&self.key);
+
+        if scoped && obj.get(self.key) == Some(&Value::null()) {
+            args.clear();
+        }
+
+        let options: Vec<(String, Node)> = self
+            .options
+            .iter()
+            .filter_map(|key| {
+                obj.get(*key)
+                    .map(|v| (key.to_string(), Node::from_query(v)))
+            })
+            .collect();
+
+        if !options.is_empty() {
+            args.push(Node::Object(options));
+        }
+
+        Node::call(self.name, args)
+    }
+}
+
+fn native_ref(id: &str) -> Option<&'static str> {
+    match id {
+        "classes" => Some("Classes"),
+        "collections" => Some("Collections"),
+        "indexes" => Some("Indexes"),
+        "databases" => Some("Databases"),
+        "functions" => Some("Functions"),
+        "keys" => Some("Keys"),
+        "tokens" => Some("Tokens"),
+        "credentials" => Some("Credentials"),
+        "roles" => Some("Roles"),
+        _ => None,
+    }
+}
+
+fn schema_ref(id: &str) -> Option<&'static str> {
+    match id {
+        "classes" => Some("Class"),
+        "collections" => Some("Collection"),
+        "indexes" => Some("Index"),
+        "databases" => Some("Database"),
+        "functions" => Some("Function"),
+        "roles" => Some("Role"),
+        _ => None,
+    }
+}
+
+fn render_number(n: crate::expr::Number) -> String {
+    use crate::expr::Number;
+
+    match n {
+        Number::UInt(u) => u.to_string(),
+        Number::Int(i) => i.to_string(),
+        Number::Double(f) => format!("{:?}", f),
+        Number::Float(f) => format!("{:?}", f),
+    }
+}
+
+fn render_key(key: &str) -> String {
+    let mut chars = key.chars();
+
+    let is_ident = match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
+    };
+
+    if is_ident {
+        key.to_string()
+    } else {
+        serde_json::to_string(key).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Printer;
+    use crate::prelude::*;
+    use chrono::{offset::TimeZone, Utc};
+    use serde_json::{self, json};
+
+    fn value(json: serde_json::Value) -> Value {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(
+            "\"Musti \\\"the cat\\\"\"",
+            value(json!("Musti \"the cat\"")).to_string()
+        );
+        assert_eq!("7", value(json!(7)).to_string());
+        assert_eq!("-7", value(json!(-7)).to_string());
+        assert_eq!("4.5", value(json!(4.5)).to_string());
+        assert_eq!("1.0", Value::from(1.0).to_string());
+        assert_eq!("true", value(json!(true)).to_string());
+        assert_eq!("null", Value::null().to_string());
+        assert_eq!(
+            "Time(\"1970-01-01T00:01:00Z\")",
+            value(json!({ "@ts": "1970-01-01T00:01:00Z" })).to_string()
+        );
+        assert_eq!(
+            "Date(\"2011-07-07\")",
+            value(json!({ "@date": "2011-07-07" })).to_string()
+        );
+        assert_eq!(
+            "Bytes(\"AQID\")",
+            value(json!({ "@bytes": "AQID" })).to_string()
+        );
+    }
+
+    #[test]
+    fn test_refs() {
+        let mut instance = Ref::instance("123");
+        instance.set_class("users");
+
+        assert_eq!(
+            "Ref(Class(\"users\"), \"123\")",
+            Value::from(instance).to_string()
+        );
+        assert_eq!(
+            "Class(\"users\")",
+            Value::from(Ref::class("users")).to_string()
+        );
+        assert_eq!(
+            "Index(\"all_users\")",
+            Value::from(Ref::index("all_users")).to_string()
+        );
+        assert_eq!(
+            "Function(\"double\")",
+            Value::from(Ref::function("double")).to_string()
+        );
+        assert_eq!(
+            "Database(\"prod\")",
+            Value::from(Ref::database("prod")).to_string()
+        );
+        assert_eq!(
+            "Classes()",
+            Value::from(Ref::instance("classes")).to_string()
+        );
+        assert_eq!(
+            "Ref(\"musti\")",
+            Value::from(Ref::instance("musti")).to_string()
+        );
+        assert_eq!(
+            "Ref(Keys(), \"1\")",
+            value(json!({ "@ref": { "class": { "@ref": { "id": "keys" } }, "id": "1" } }))
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_collections() {
+        let doc = value(json!({
+            "name": "Musti",
+            "nicknames": ["mustu", "muspus"],
+            "not an ident": {},
+        }));
+
+        assert_eq!(
+            "{ name: \"Musti\", nicknames: [\"mustu\", \"muspus\"], \"not an ident\": {} }",
+            doc.to_string()
+        );
+    }
+
+    #[test]
+    fn test_query() {
+        let query = value(json!({
+            "@query": {
+                "lambda": ["x", "y"],
+                "expr": {
+                    "let": { "sum": { "add": [{ "var": "x" }, { "var": "y" }] } },
+                    "in": {
+                        "object": {
+                            "sum": { "var": "sum" },
+                            "page": {
+                                "paginate": { "match": { "@ref": { "id": "all_cats" } } },
+                                "size": 10
+                            }
+                        }
+                    }
+                }
+            }
+        }));
+
+        assert_eq!(
+            "Query(Lambda([\"x\", \"y\"], Let({ sum: Add(Var(\"x\"), Var(\"y\")) }, \
+             { page: Paginate(Match(Ref(\"all_cats\")), { size: 10 }), sum: Var(\"sum\") })))",
+            query.to_string()
+        );
+
+        let map = value(json!({
+            "@query": {
+                "map": { "lambda": "x", "expr": { "var": "x" } },
+                "collection": [1, 2]
+            }
+        }));
+
+        assert_eq!(
+            "Query(Map([1, 2], Lambda(\"x\", Var(\"x\"))))",
+            map.to_string()
+        );
+    }
+
+    #[test]
+    fn test_expr() {
+        let mut params = Object::default();
+        params.insert("name", "Musti");
+        params.insert("born", Utc.timestamp_opt(60, 0).unwrap());
+
+        let query = Do::new(Create::new(Ref::class("cats"), params))
+            .push(If::cond(
+                Exists::new(Ref::instance("1")),
+                Delete::new(Ref::instance("1")),
+                Expr::null(),
+            ))
+            .push(Expr::from(Var::new("x")).into_quoted())
+            .clone();
+
+        assert_eq!(
+            "Do(Create(Class(\"cats\"), { data: { born: Time(\"1970-01-01T00:01:00Z\"), name: \"Musti\" } }), \
+             If(Exists(Ref(\"1\")), Delete(Ref(\"1\")), null), Query(Var(\"x\")))",
+            Query::from(query.clone()).to_string()
+        );
+
+        assert_eq!(
+            "Do(\n  \
+               Create(\n    \
+                 Class(\"cats\"),\n    \
+                 { data: { born: Time(\"1970-01-01T00:01:00Z\"), name: \"Musti\" } }\n  \
+               ),\n  \
+               If(Exists(Ref(\"1\")), Delete(Ref(\"1\")), null),\n  \
+               Query(Var(\"x\"))\n\
+             )",
+            format!("{:#}", Expr::from(query))
+        );
+    }
+
+    #[test]
+    fn test_expr_parses_back() {
+        let queries = vec![
+            Expr::from(Paginate::new(
+                Match::new(Index::find("cats_by_age")).with_terms(7),
+            )),
+            Expr::from(Let::bindings(
+                vec![Binding::new("x", Add::new(Array::from(vec![1, 2])))],
+                Lambda::new(
+                    Array::from(vec!["a", "b"]),
+                    ReplaceStr::new(Var::new("a"), "_", Var::new("x")),
+                ),
+            )),
+            Expr::from(Select::new(
+                vec!["data", "name"],
+                Get::instance(Ref::instance("1")),
+            )),
+        ];
+
+        for query in queries {
+            let parsed = crate::fql::parse(&query.to_string()).unwrap();
+
+            assert_eq!(
+                serde_json::to_value(&query).unwrap(),
+                serde_json::to_value(&parsed).unwrap(),
+                "{}",
+                query
+            );
+        }
+    }
+
+    #[test]
+    fn test_set() {
+        let set = value(json!({
+            "@set": {
+                "union": [
+                    { "@set": { "match": { "@ref": { "class": { "@ref": { "id": "indexes" } }, "id": "cats" } }, "terms": 7 } },
+                    { "@set": { "match": { "@ref": { "class": { "@ref": { "id": "indexes" } }, "id": "dogs" } } } }
+                ]
+            }
+        }));
+
+        assert_eq!(
+            "Union(Match(Index(\"cats\"), 7), Match(Index(\"dogs\")))",
+            set.to_string()
+        );
+    }
+
+    #[test]
+    fn test_pretty() {
+        let doc = value(json!({
+            "name": "Musti",
+            "owners": [
+                { "name": "Julius de Bruijn", "email": "julius@example.com" },
+                { "name": "Naukio", "email": "naukio@example.com" }
+            ]
+        }));
+
+        let expected = r#"{
+  name: "Musti",
+  owners: [
+    { email: "julius@example.com", name: "Julius de Bruijn" },
+    { email: "naukio@example.com", name: "Naukio" }
+  ]
+}"#;
+
+        assert_eq!(expected, format!("{:#}", doc));
+
+        let mut printer = Printer::new();
+        printer.indent(4).width(30);
+
+        let expected = r#"{
+    name: "Musti",
+    owners: [
+        {
+            email: "julius@example.com",
+            name: "Julius de Bruijn"
+        },
+        {
+            email: "naukio@example.com",
+            name: "Naukio"
+        }
+    ]
+}"#;
+
+        assert_eq!(expected, printer.print(&doc));
+    }
+}
@@ -0,0 +1,1009 @@
+//! Parsing FQL text into an expression.
+use super::{Function, FUNCTIONS};
+use crate::{
+    error::{Error, ParseError},
+    expr::{Expr, Ref},
+    query::Query,
+};
+use serde_json::{Map, Number, Value as Json};
+use std::{fmt, iter::Peekable, str::Chars};
+
+/// Parses a query in the FQL syntax of the Fauna shell into an expression,
+/// using the functions of the [query](../query/index.html) module.
+///
+/// Object literals are written as `{ key: value }`, and optional parameters,
+/// such as the size of `Paginate`, as an object after the arguments.
+/// `Ref(Class("users"), "123")` and the other refs with literal ids are read
+/// as ref values, and `Query(...)` as a quoted query.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// # use serde_json::json;
+/// #
+/// let expr = faunadb::fql::parse(
+///     r#"Paginate(Match(Index("users_by_email"), "a@b.c"), { size: 10 })"#,
+/// ).unwrap();
+///
+/// assert_eq!(
+///     json!({
+///         "paginate": {
+///             "match": { "index": "users_by_email" },
+///             "terms": "a@b.c"
+///         },
+///         "size": 10,
+///         "events": false,
+///         "sources": false
+///     }),
+///     serde_json::to_value(&expr).unwrap(),
+/// );
+///
+/// let error = faunadb::fql::parse("Get(\n  Ref(Class(\"users\") \"1\"))").unwrap_err();
+/// assert_eq!(
+///     "Couldn't parse FQL: expected `,` or `)`, found a string at line 2, column 22",
+///     error.to_string(),
+/// );
+/// ```
+pub fn parse(fql: &str) -> crate::Result<Expr<'static>> {
+    let ast = Parser::new(fql)
+        .and_then(|mut parser| parser.parse())
+        .map_err(Error::ParseError)?;
+
+    let position = ast.position;
+    let json = ast.lower().map_err(Error::ParseError)?;
+
+    Expr::from_json(json).map_err(|e| Error::ParseError(position.error(e.to_string())))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    fn error(self, message: impl Into<String>) -> ParseError {
+        ParseError::new(message, self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    Comma,
+    Colon,
+    Str(String),
+    Num(Number),
+    Ident(String),
+    Eof,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::LParen => write!(f, "`(`"),
+            Token::RParen => write!(f, "`)`"),
+            Token::LBracket => write!(f, "`[`"),
+            Token::RBracket => write!(f, "`]`"),
+            Token::LBrace => write!(f, "`{{`"),
+            Token::RBrace => write!(f, "`}}`"),
+            Token::Comma => write!(f, "`,`"),
+            Token::Colon => write!(f, "`:`"),
+            Token::Str(_) => write!(f, "a string"),
+            Token::Num(_) => write!(f, "a number"),
+            Token::Ident(ident) => write!(f, "`{}`", ident),
+            Token::Eof => write!(f, "the end of input"),
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(fql: &'a str) -> Self {
+        Self {
+            chars: fql.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(c)
+    }
+
+    fn tokens(mut self) -> Result<Vec<(Token, Position)>, ParseError> {
+        let mut tokens = Vec::new();
+
+        loop {
+            self.skip_whitespace()?;
+
+            let position = self.position();
+
+            let token = match self.chars.peek().cloned() {
+                None => {
+                    tokens.push((Token::Eof, position));
+                    return Ok(tokens);
+                }
+                Some(c) => match c {
+                    '(' | ')' | '[' | ']' | '{' | '}' | ',' | ':' => {
+                        self.bump();
+
+                        match c {
+                            '(' => Token::LParen,
+                            ')' => Token::RParen,
+                            '[' => Token::LBracket,
+                            ']' => Token::RBracket,
+                            '{' => Token::LBrace,
+                            '}' => Token::RBrace,
+                            ',' => Token::Comma,
+                            _ => Token::Colon,
+                        }
+                    }
+                    '"' | '\'' => Token::Str(self.string(position)?),
+                    '-' | '0'..='9' => Token::Num(self.number(position)?),
+                    c if c.is_ascii_alphabetic() || c == '_' => Token::Ident(self.ident()),
+                    c => return Err(position.error(format!("unexpected character `{}`", c))),
+                },
+            };
+
+            tokens.push((token, position));
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), ParseError> {
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.bump();
+                continue;
+            }
+
+            if c != '/' {
+                break;
+            }
+
+            let position = self.position();
+            self.bump();
+
+            match self.bump() {
+                Some('/') => {
+                    while self.chars.peek().map(|&c| c != '\n').unwrap_or(false) {
+                        self.bump();
+                    }
+                }
+                Some('*') => loop {
+                    match self.bump() {
+                        Some('*') if self.chars.peek() == Some(&'/') => {
+                            self.bump();
+                            break;
+                        }
+                        Some(_) => (),
+                        None => return Err(position.error("unterminated comment")),
+                    }
+                },
+                _ => return Err(position.error("unexpected character `/`")),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn string(&mut self, start: Position) -> Result<String, ParseError> {
+        let quote = self.bump().unwrap();
+        let mut s = String::new();
+
+        loop {
+            let position = self.position();
+
+            match self.bump() {
+                None => return Err(start.error("unterminated string")),
+                Some(c) if c == quote => return Ok(s),
+                Some('\\') => match self.bump() {
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    Some('r') => s.push('\r'),
+                    Some('b') => s.push('\u{8}'),
+                    Some('f') => s.push('\u{c}'),
+                    Some('u') => s.push(self.unicode_escape(position)?),
+                    Some(c @ '"') | Some(c @ '\'') | Some(c @ '\\') | Some(c @ '/') => s.push(c),
+                    _ => return Err(position.error("invalid escape in a string")),
+                },
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self, position: Position) -> Result<char, ParseError> {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            let digit = self
+                .bump()
+                .and_then(|c| c.to_digit(16))
+                .ok_or_else(|| position.error("invalid unicode escape in a string"))?;
+
+            code = code * 16 + digit;
+        }
+
+        std::char::from_u32(code)
+            .ok_or_else(|| position.error("invalid unicode escape in a string"))
+    }
+
+    fn number(&mut self, start: Position) -> Result<Number, ParseError> {
+        let mut text = String::new();
+
+        if self.chars.peek() == Some(&'-') {
+            text.push('-');
+            self.bump();
+        }
+
+        while let Some(&c) = self.chars.peek() {
+            let exponent_sign = (c == '-' || c == '+') && text.ends_with(|c| "eE".contains(c));
+
+            if c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || exponent_sign {
+                text.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        let invalid = || start.error(format!("invalid number `{}`", text));
+
+        if text.contains(|c| ".eE".contains(c)) {
+            text.parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .ok_or_else(invalid)
+        } else if let Ok(i) = text.parse::<i64>() {
+            Ok(Number::from(i))
+        } else {
+            text.parse::<u64>().map(Number::from).map_err(|_| invalid())
+        }
+    }
+
+    fn ident(&mut self) -> String {
+        let mut ident = String::new();
+
+        while let Some(&c) = self.chars.peek() {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                ident.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        ident
+    }
+}
+
+/// The syntax tree of the text, before turning it into the wire format.
+#[derive(Debug)]
+struct Ast {
+    node: AstNode,
+    position: Position,
+}
+
+#[derive(Debug)]
+enum AstNode {
+    Literal(Json),
+    Array(Vec<Ast>),
+    Object(Vec<(String, Ast)>),
+    Call(String, Vec<Ast>),
+}
+
+struct Parser {
+    tokens: Vec<(Token, Position)>,
+    index: usize,
+}
+
+impl Parser {
+    fn new(fql: &str) -> Result<Self, ParseError> {
+        let tokens = Lexer::new(fql).tokens()?;
+
+        Ok(Self { tokens, index: 0 })
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn next(&mut self) -> (Token, Position) {
+        let token = self.tokens[self.index].clone();
+
+        if self.index < self.tokens.len() - 1 {
+            self.index += 1;
+        }
+
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ParseError> {
+        let (token, position) = self.next();
+
+        if token == expected {
+            Ok(())
+        } else {
+            Err(position.error(format!("expected {}, found {}", expected, token)))
+        }
+    }
+
+    fn parse(&mut self) -> Result<Ast, ParseError> {
+        let ast = self.expr()?;
+        self.expect(Token::Eof)?;
+
+        Ok(ast)
+    }
+
+    fn expr(&mut self) -> Result<Ast, ParseError> {
+        let (token, position) = self.next();
+
+        let node = match token {
+            Token::Str(s) => AstNode::Literal(Json::String(s)),
+            Token::Num(n) => AstNode::Literal(Json::Number(n)),
+            Token::LBracket => AstNode::Array(self.list(Token::RBracket)?),
+            Token::LBrace => AstNode::Object(self.fields()?),
+            Token::Ident(ident) => match ident.as_str() {
+                "null" => AstNode::Literal(Json::Null),
+                "true" => AstNode::Literal(Json::Bool(true)),
+                "false" => AstNode::Literal(Json::Bool(false)),
+                _ => {
+                    self.expect(Token::LParen)?;
+                    AstNode::Call(ident, self.list(Token::RParen)?)
+                }
+            },
+            token => return Err(position.error(format!("expected an expression, found {}", token))),
+        };
+
+        Ok(Ast { node, position })
+    }
+
+    /// Comma-separated expressions up to the closing token, allowing a
+    /// trailing comma.
+    fn list(&mut self, close: Token) -> Result<Vec<Ast>, ParseError> {
+        let mut items = Vec::new();
+
+        loop {
+            if *self.peek() == close {
+                self.next();
+                return Ok(items);
+            }
+
+            items.push(self.expr()?);
+
+            let (token, position) = self.next();
+
+            match token {
+                Token::Comma => (),
+                ref token if *token == close => return Ok(items),
+                token => {
+                    return Err(
+                        position.error(format!("expected `,` or {}, found {}", close, token))
+                    )
+                }
+            }
+        }
+    }
+
+    fn fields(&mut self) -> Result<Vec<(String, Ast)>, ParseError> {
+        let mut fields = Vec::new();
+
+        loop {
+            let (token, position) = self.next();
+
+            let key = match token {
+                Token::RBrace => return Ok(fields),
+                Token::Ident(key) | Token::Str(key) => key,
+                token => {
+                    return Err(position.error(format!("expected a key, found {}", token)));
+                }
+            };
+
+            self.expect(Token::Colon)?;
+            fields.push((key, self.expr()?));
+
+            let (token, position) = self.next();
+
+            match token {
+                Token::Comma => (),
+                Token::RBrace => return Ok(fields),
+                token => {
+                    return Err(position.error(format!("expected `,` or `}}`, found {}", token)))
+                }
+            }
+        }
+    }
+}
+
+impl Ast {
+    /// Turns the tree into the wire format of the query.
+    fn lower(self) -> Result<Json, ParseError> {
+        match self.node {
+            AstNode::Literal(json) => Ok(json),
+            AstNode::Array(items) => Ok(Json::Array(lower_all(items)?)),
+            AstNode::Object(fields) => {
+                let mut obj = Map::new();
+                obj.insert(String::from("object"), lower_fields(fields)?);
+
+                Ok(Json::Object(obj))
+            }
+            AstNode::Call(name, args) => lower_call(name, args, self.position),
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self.node {
+            AstNode::Literal(Json::String(ref s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// The path of a ref with literal ids, e.g. `classes/users/123` for
+    /// `Ref(Class("users"), "123")`.
+    fn ref_path(&self) -> Option<String> {
+        let (name, args) = match self.node {
+            AstNode::Call(ref name, ref args) => (name.as_str(), args),
+            _ => return None,
+        };
+
+        let scoped = |path: String, scope: Option<&Ast>| match scope {
+            None => Some(path),
+            Some(scope) => scope
+                .ref_path()
+                .filter(|scope| scope.starts_with("databases/"))
+                .map(|scope| format!("{}/{}", scope, path)),
+        };
+
+        if let Some(native) = native_id(name) {
+            return match args.as_slice() {
+                [] => Some(native.to_string()),
+                [scope] => scoped(native.to_string(), Some(scope)),
+                _ => None,
+            };
+        }
+
+        match (name, args.as_slice()) {
+            ("Ref", [path]) => path.as_str().map(String::from),
+            ("Ref", [parent, id]) => {
+                let id = id
+                    .as_str()
+                    .filter(|id| !id.is_empty() && !id.contains('/'))?;
+                parent.ref_path().map(|parent| format!("{}/{}", parent, id))
+            }
+            (name, [id]) | (name, [id, _]) => {
+                let class = schema_id(name)?;
+                let id = id
+                    .as_str()
+                    .filter(|id| !id.is_empty() && !id.contains('/'))?;
+
+                scoped(format!("{}/{}", class, id), args.get(1))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn lower_all(items: Vec<Ast>) -> Result<Vec<Json>, ParseError> {
+    items.into_iter().map(Ast::lower).collect()
+}
+
+fn lower_fields(fields: Vec<(String, Ast)>) -> Result<Json, ParseError> {
+    let mut obj = Map::new();
+
+    for (key, value) in fields {
+        obj.insert(key, value.lower()?);
+    }
+
+    Ok(Json::Object(obj))
+}
+
+fn lower_call(name: String, mut args: Vec<Ast>, position: Position) -> Result<Json, ParseError> {
+    match name.as_str() {
+        "Ref" | "Collection" | "Collections" | "Keys" | "Tokens" | "Credentials" | "Role"
+        | "Roles" => {
+            let call = Ast {
+                node: AstNode::Call(name, args),
+                position,
+            };
+
+            return match call.ref_path() {
+                Some(path) => ref_json(&path, position),
+                None => lower_dynamic_ref(call),
+            };
+        }
+        "Bytes" => {
+            return match args.as_slice() {
+                [bytes] if bytes.as_str().map(is_base64).unwrap_or(false) => {
+                    Ok(annotated("@bytes", args.remove(0).lower()?))
+                }
+                _ => Err(position.error("`Bytes` expects a base64 encoded string")),
+            };
+        }
+        "Query" => {
+            return match args.len() {
+                1 => Ok(annotated("@query", args.remove(0).lower()?)),
+                n => Err(position.error(format!("`Query` expects {}, got {}", arguments(1), n))),
+            };
+        }
+        _ => (),
+    }
+
+    let function = FUNCTIONS
+        .iter()
+        .find(|function| function.name == name)
+        .ok_or_else(|| position.error(format!("unknown function `{}`", name)))?;
+
+    let mut obj = Map::new();
+
+    if let Some(options) = take_options(function, &mut args) {
+        for (key, value) in options {
+            obj.insert(key, value.lower()?);
+        }
+    }
+
+    if function.variadic {
+        // A single argument is passed as is, e.g. `Add([1, 2])`.
+        let value = if args.len() == 1 {
+            args.remove(0).lower()?
+        } else {
+            Json::Array(lower_all(args)?)
+        };
+
+        obj.insert(function.key.to_string(), value);
+        return checked_call(&name, Json::Object(obj), position);
+    }
+
+    if args.len() > function.args.len() {
+        return Err(position.error(format!(
+            "`{}` expects at most {}, got {}",
+            name,
+            arguments(function.args.len()),
+            args.len()
+        )));
+    }
+
+    if args.is_empty() {
+        obj.insert(function.key.to_string(), Json::Null);
+    }
+
+    for (key, arg) in function.args.iter().zip(args) {
+        let value = match arg.node {
+            // The bindings of `Let` are a plain object.
+            AstNode::Object(fields) if function.key == "let" && *key == "let" => {
+                lower_fields(fields)?
+            }
+            _ => arg.lower()?,
+        };
+
+        obj.insert(key.to_string(), value);
+    }
+
+    checked_call(&name, Json::Object(obj), position)
+}
+
+/// Checks the arguments have the types the function expects, so the error
+/// points to the call instead of the whole query.
+fn checked_call(name: &str, json: Json, position: Position) -> Result<Json, ParseError> {
+    match Query::from_json(&json) {
+        Ok(_) => Ok(json),
+        Err(e) => Err(position.error(format!("invalid arguments for `{}`: {}", name, e))),
+    }
+}
+
+/// Removes a trailing object of optional parameters from the arguments.
+fn take_options(function: &Function, args: &mut Vec<Ast>) -> Option<Vec<(String, Ast)>> {
+    let is_options = match args.last() {
+        Some(Ast {
+            node: AstNode::Object(fields),
+            ..
+        }) => {
+            args.len() > 1
+                && !function.options.is_empty()
+                && fields
+                    .iter()
+                    .all(|(key, _)| function.options.contains(&key.as_str()))
+        }
+        _ => false,
+    };
+
+    if !is_options {
+        return None;
+    }
+
+    match args.pop() {
+        Some(Ast {
+            node: AstNode::Object(fields),
+            ..
+        }) => Some(fields),
+        _ => None,
+    }
+}
+
+/// A ref with computed parts, written as the `ref` function.
+fn lower_dynamic_ref(call: Ast) -> Result<Json, ParseError> {
+    let position = call.position;
+
+    let (name, mut args) = match call.node {
+        AstNode::Call(name, args) => (name, args),
+        _ => unreachable!(),
+    };
+
+    if name != "Ref" || args.len() != 2 {
+        return Err(position.error(format!("invalid arguments for `{}`", name)));
+    }
+
+    let id = args.pop().unwrap().lower()?;
+    let class = args.pop().unwrap().lower()?;
+
+    let mut obj = Map::new();
+    obj.insert(String::from("ref"), class);
+    obj.insert(String::from("id"), id);
+
+    Ok(Json::Object(obj))
+}
+
+fn ref_json(path: &str, position: Position) -> Result<Json, ParseError> {
+    let reference: Ref<'static> = path
+        .parse()
+        .map_err(|e| position.error(format!("invalid ref: {}", e)))?;
+
+    Ok(annotated("@ref", serde_json::to_value(&reference).unwrap()))
+}
+
+fn arguments(n: usize) -> String {
+    match n {
+        1 => String::from("1 argument"),
+        n => format!("{} arguments", n),
+    }
+}
+
+fn annotated(key: &str, value: Json) -> Json {
+    let mut obj = Map::new();
+    obj.insert(key.to_string(), value);
+
+    Json::Object(obj)
+}
+
+fn is_base64(s: &str) -> bool {
+    base64::decode(s).is_ok()
+}
+
+fn native_id(name: &str) -> Option<&'static str> {
+    match name {
+        "Collections" => Some("collections"),
+        "Keys" => Some("keys"),
+        "Tokens" => Some("tokens"),
+        "Credentials" => Some("credentials"),
+        "Roles" => Some("roles"),
+        "Classes" => Some("classes"),
+        "Indexes" => Some("indexes"),
+        "Databases" => Some("databases"),
+        "Functions" => Some("functions"),
+        _ => None,
+    }
+}
+
+fn schema_id(name: &str) -> Option<&'static str> {
+    match name {
+        "Class" => Some("classes"),
+        "Collection" => Some("collections"),
+        "Index" => Some("indexes"),
+        "Database" => Some("databases"),
+        "Function" => Some("functions"),
+        "Role" => Some("roles"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::prelude::*;
+    use chrono::{offset::TimeZone, Utc};
+    use serde_json::{self, json};
+
+    fn wire(fql: &str) -> serde_json::Value {
+        serde_json::to_value(&parse(fql).unwrap()).unwrap()
+    }
+
+    fn error(fql: &str) -> (String, usize, usize) {
+        match parse(fql) {
+            Err(crate::error::Error::ParseError(e)) => {
+                (e.message().to_string(), e.line(), e.column())
+            }
+            result => panic!("expected a parse error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(json!(null), wire("null"));
+        assert_eq!(json!(true), wire(" true "));
+        assert_eq!(json!(-12), wire("-12"));
+        assert_eq!(json!(1.5e3), wire("1.5e3"));
+        assert_eq!(json!("it's \"quoted\"\n"), wire(r#"'it\'s "quoted"\n'"#));
+        assert_eq!(json!([1, "two", [3]]), wire("[1, 'two', [3],]"));
+
+        assert_eq!(
+            json!({ "object": { "name": "Musti", "age 2": { "object": {} } } }),
+            wire(r#"{ name: "Musti", "age 2": {} }"#)
+        );
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(json!({ "add": [1, 2, 3] }), wire("Add(1, 2, 3)"));
+        assert_eq!(json!({ "add": [1, 2] }), wire("Add([1, 2])"));
+        assert_eq!(json!({ "classes": null }), wire("Classes()"));
+
+        assert_eq!(
+            json!({ "map": { "lambda": "x", "expr": { "var": "x" } }, "collection": [1] }),
+            wire(r#"Map([1], Lambda("x", Var("x")))"#)
+        );
+
+        assert_eq!(
+            json!({ "select": ["data", "name"], "from": { "var": "cat" } }),
+            wire(r#"Select(["data", "name"], Var("cat"))"#)
+        );
+
+        assert_eq!(
+            json!({
+                "let": { "x": 1, "y": { "var": "x" } },
+                "in": { "replacestr": "a_b", "find": "_", "replace": " " }
+            }),
+            wire(r#"Let({ x: 1, y: Var("x") }, ReplaceStr("a_b", "_", " "))"#)
+        );
+
+        assert_eq!(
+            json!({
+                "lambda": ["a", "b"],
+                "expr": { "if": { "gt": [{ "var": "a" }, { "var": "b" }] }, "then": "a", "else": "b" }
+            }),
+            wire(
+                r#"
+                // The larger one
+                Lambda(["a", "b"], If(GT(Var("a"), Var("b")), "a", /* or */ "b"))
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn test_refs_and_annotations() {
+        assert_eq!(
+            json!({
+                "get": {
+                    "@ref": {
+                        "class": { "@ref": { "class": { "@ref": { "id": "classes" } }, "id": "users" } },
+                        "id": "123"
+                    }
+                }
+            }),
+            wire(r#"Get(Ref(Class("users"), "123"))"#)
+        );
+
+        assert_eq!(
+            serde_json::to_value(&Expr::from(Ref::index("all_users"))).unwrap(),
+            wire(r#"Ref("indexes/all_users")"#)
+        );
+
+        assert_eq!(
+            json!({ "ref": { "var": "class" }, "id": "1" }),
+            wire(r#"Ref(Var("class"), "1")"#)
+        );
+
+        assert_eq!(json!({ "@bytes": "AQID" }), wire(r#"Bytes("AQID")"#));
+
+        assert_eq!(
+            json!({ "@query": { "lambda": "x", "expr": { "var": "x" } } }),
+            wire(r#"Query(Lambda("x", Var("x")))"#)
+        );
+    }
+
+    #[test]
+    fn test_typed_queries() {
+        match parse(r#"Paginate(Match(Index("cats"), 7), { size: 2, after: [1] })"#).unwrap() {
+            Expr::Query(query) => match *query {
+                Query::Paginate(_) => (),
+                query => panic!("expected paginate, got {:?}", query),
+            },
+            expr => panic!("expected a query, got {:?}", expr),
+        }
+
+        let serialized = wire(r#"Paginate(Match(Index("cats"), 7), { size: 2, after: [1] })"#);
+
+        assert_eq!(json!(2), serialized["size"]);
+        assert_eq!(json!([1]), serialized["after"]);
+    }
+
+    #[test]
+    fn test_printed_round_trip() {
+        let body = json!({
+            "lambda": "cat",
+            "expr": {
+                "map": { "lambda": "ref", "expr": { "get": { "var": "ref" } } },
+                "collection": {
+                    "paginate": {
+                        "match": { "index": "cats_by_owner" },
+                        "terms": { "select": ["data", "owner"], "from": { "var": "cat" } }
+                    },
+                    "size": 10,
+                    "events": false,
+                    "sources": false
+                }
+            }
+        });
+
+        let value: Value = serde_json::from_value(json!({ "@query": body.clone() })).unwrap();
+        let printed = format!("{:#}", value);
+
+        assert_eq!(json!({ "@query": body }), wire(&printed));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            (String::from("unknown function `Gett`"), 2, 3),
+            error("\n  Gett(Ref(\"a\"))")
+        );
+
+        assert_eq!(
+            (String::from("expected `,` or `)`, found `]`"), 1, 7),
+            error("Add(1 ]")
+        );
+
+        assert_eq!(
+            (String::from("unterminated string"), 1, 5),
+            error("Var(\"x)")
+        );
+
+        assert_eq!(
+            (
+                String::from("`Var` expects at most 1 argument, got 2"),
+                1,
+                1
+            ),
+            error("Var(\"x\", \"y\")")
+        );
+
+        assert_eq!(
+            (String::from("expected the end of input, found `)`"), 1, 9),
+            error("Var(\"x\"))")
+        );
+
+        assert_eq!((String::from("unexpected character `@`"), 1, 1), error("@"));
+
+        let (message, line, column) = error("Add(1,\n  Casefold(\"a\", 1))");
+
+        assert!(message.starts_with("invalid arguments for `Casefold`"));
+        assert_eq!((2, 3), (line, column));
+    }
+
+    #[test]
+    fn test_macro() {
+        let owner = Ref::instance("naukio");
+        let size = 10;
+
+        let expr = crate::fql!(Map(
+            Paginate(Match(Index("cats_by_owner"), #owner), { size: #size }),
+            Lambda("cat", Let({ name: Select(["data", "name"], Get(Var("cat"))) }, {
+                "cat name": Var("name"),
+                tags: ["a", "b",],
+                weight: -4.5,
+                indoor: true,
+                collar: null,
+            })),
+        ));
+
+        let text = r#"Map(
+            Paginate(Match(Index("cats_by_owner"), Ref("naukio")), { size: 10 }),
+            Lambda("cat", Let({ name: Select(["data", "name"], Get(Var("cat"))) }, {
+                "cat name": Var("name"),
+                tags: ["a", "b",],
+                weight: -4.5,
+                indoor: true,
+                collar: null,
+            })),
+        )"#;
+
+        assert_eq!(wire(text), serde_json::to_value(&expr).unwrap());
+
+        let ts = Utc.timestamp_opt(60, 0).unwrap();
+        let mut get = Get::instance(Ref::instance("musti"));
+        get.timestamp(ts);
+
+        assert_eq!(
+            Expr::from(get),
+            crate::fql!(Get(#(Ref::instance("musti")), #ts))
+        );
+
+        assert_eq!(
+            Expr::from(Call::new(Ref::function("double"), 2)),
+            crate::fql!(Call(Function("double"), 2))
+        );
+
+        assert_eq!(
+            Expr::from(Epoch::new(5, EpochUnit::Second)),
+            crate::fql!(Epoch(5, "second"))
+        );
+    }
+
+    #[test]
+    fn test_macro_matches_parse() {
+        macro_rules! same {
+            ($($tt:tt)+) => {
+                assert_eq!(
+                    wire(stringify!($($tt)+)),
+                    serde_json::to_value(&crate::fql!($($tt)+)).unwrap(),
+                    "{}",
+                    stringify!($($tt)+),
+                )
+            };
+        }
+
+        same!(Paginate(Classes()));
+        same!(Paginate(Match(Index("cats"), 7), { size: 2, after: [1], events: true }));
+        same!(Get(Ref(Class("cats"), "musti")));
+        same!(Exists(Ref(Class("cats"), "musti")));
+        same!(Do(1, 2, 3));
+        same!(If(true, "yes", "no"));
+        same!(Lambda(["x", "y"], Add(Var("x"), Var("y"))));
+        same!(Add([1, 2]));
+        same!(Multiply(1, 2.5, 3));
+        same!(And(true, false, true));
+        same!(LT(1, 2));
+        same!(Contains(["data", "name"], { data: { name: "Musti" } }));
+        same!(Select(["data"], { data: 1 }, 0));
+        same!(SelectAll(["a"], [{ a: 1 }]));
+        same!(Filter([1, 2], Lambda("x", GTE(Var("x"), 2))));
+        same!(Foreach([1], Lambda("x", Var("x"))));
+        same!(Append([1], [2]));
+        same!(Prepend([1], [2]));
+        same!(Take(1, [1, 2]));
+        same!(Drop(1, [1, 2]));
+        same!(Union(
+            Match(Index("a")),
+            Match(Index("b")),
+            Match(Index("c"))
+        ));
+        same!(Join(Match(Index("a")), Index("b")));
+        same!(Hypot(3));
+        same!(Hypot(3, 4));
+        same!(Round(1.5, 1));
+        same!(Casefold("a", "NFKC"));
+        same!(FindStrRegex("abc", "b", 1, 2));
+        same!(ReplaceStrRegex("abc", "b", "c"));
+        same!(SubString("abc", 1, 1));
+        same!(Class("cats", Database("db")));
+        same!(Indexes(Database("db")));
+        same!(Create(Class("cats"), { data: { name: "Musti" } }));
+        same!(Replace(Ref(Class("cats"), "musti"), { data: {} }));
+        same!(Query(Lambda("x", Var("x"))));
+        same!(ToString(NewId()));
+    }
+}
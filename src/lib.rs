@@ -66,14 +66,24 @@ extern crate log;
 mod macros;
 
 pub mod client;
+pub mod document;
 pub mod error;
 pub mod expr;
+pub mod fql;
 pub mod prelude;
 pub mod query;
 
-mod serde;
+pub mod serde;
 
 #[cfg(test)]
 mod test_utils;
 
 pub type Result<T> = ::std::result::Result<T, error::Error>;
+
+pub use crate::{
+    document::FaunaDocument,
+    serde::{de::from_value, ser::to_expr},
+};
+
+#[cfg(feature = "derive")]
+pub use faunadb_derive::FaunaDocument;
@@ -28,6 +28,36 @@ macro_rules! boxed_query {
     );
 }
 
+/// A helper macro to implement `IntoOwned` for the given structs by
+/// converting every listed field, or for enums of single-field variants.
+#[macro_export]
+macro_rules! into_owned {
+    (enum $kind:ident { $($variant:ident),* $(,)* }) => (
+        impl<'a> $crate::expr::IntoOwned for $kind<'a> {
+            type Owned = $kind<'static>;
+
+            fn into_owned(self) -> $kind<'static> {
+                match self {
+                    $($kind::$variant(v) => $kind::$variant($crate::expr::IntoOwned::into_owned(v)),)*
+                }
+            }
+        }
+    );
+    ($($kind:ident { $($field:tt),* $(,)* }),* $(,)*) => (
+        $(
+            impl<'a> $crate::expr::IntoOwned for $kind<'a> {
+                type Owned = $kind<'static>;
+
+                fn into_owned(self) -> $kind<'static> {
+                    $kind {
+                        $($field: $crate::expr::IntoOwned::into_owned(self.$field),)*
+                    }
+                }
+            }
+        )*
+    );
+}
+
 /// A convenience to convert a type of a signed integer into Fauna `Expr`.
 #[macro_export]
 macro_rules! int_expr {
@@ -67,3 +97,457 @@ macro_rules! uint_expr {
         )*
     );
 }
+
+/// Builds an `Expr` from FQL written as Rust tokens, the same syntax
+/// [parse](fql/fn.parse.html) reads from text. Every call expands into its
+/// query builder, so an unknown function or an unsupported number of
+/// arguments is a compile error. Rust values are interpolated with `#name`,
+/// or `#(expression)`, and passed to the builders as they are.
+///
+/// ```
+/// # use faunadb::{fql, prelude::*};
+/// # use serde_json::json;
+/// #
+/// let r = Ref::instance("musti");
+/// let expr = fql!(If(Exists(#(r.clone())), Get(#r), null));
+///
+/// assert_eq!(
+///     json!({
+///         "if": { "exists": { "@ref": { "id": "musti" } } },
+///         "then": { "get": { "@ref": { "id": "musti" } } },
+///         "else": null
+///     }),
+///     serde_json::to_value(&expr).unwrap(),
+/// );
+///
+/// let name = "Musti";
+/// let expr = fql!({ name: #name, tags: ["a", "b"], age: -3 });
+///
+/// assert_eq!(
+///     json!({ "object": { "name": "Musti", "tags": ["a", "b"], "age": -3 } }),
+///     serde_json::to_value(&expr).unwrap(),
+/// );
+/// ```
+///
+/// Arguments the builders take as Rust types, such as the `DateTime` of
+/// `Get` or the params of `CreateClass`, are interpolated. Epoch units,
+/// normalizers and write actions may also be given as string literals.
+///
+/// ```compile_fail
+/// # use faunadb::fql;
+/// #
+/// let expr = fql!(Gett(Ref("a")));
+/// ```
+#[macro_export]
+macro_rules! fql {
+    (@value null) => ($crate::expr::Expr::null());
+    (@value [$($tt:tt)*]) => ($crate::expr::Array($crate::fql!(@array [] $($tt)*)));
+    (@value {$($tt:tt)*}) => ({
+        let mut object = $crate::expr::Object::default();
+        $crate::fql!(@insert object $($tt)*);
+        object
+    });
+    (@value # $value:ident) => ($value);
+    (@value # ($value:expr)) => ($value);
+    (@value - $lit:literal) => (-$lit);
+    (@value $function:ident ($($tt:tt)*)) => ($crate::fql!(@args $function [] $($tt)*));
+    (@value $lit:literal) => ($lit);
+
+    (@array [$($out:expr,)*]) => (vec![$($out,)*]);
+    (@array [$($out:expr,)*] $a:tt $(, $($rest:tt)*)?) => (
+        $crate::fql!(
+            @array [$($out,)* $crate::expr::Expr::from($crate::fql!(@value $a)),]
+            $($($rest)*)?
+        )
+    );
+    (@array [$($out:expr,)*] $a:tt $b:tt $(, $($rest:tt)*)?) => (
+        $crate::fql!(
+            @array [$($out,)* $crate::expr::Expr::from($crate::fql!(@value $a $b)),]
+            $($($rest)*)?
+        )
+    );
+
+    (@insert $object:ident) => {};
+    (@insert $object:ident $key:tt : $a:tt $(, $($rest:tt)*)?) => {
+        $object.insert($crate::fql!(@key $key), $crate::fql!(@value $a));
+        $crate::fql!(@insert $object $($($rest)*)?);
+    };
+    (@insert $object:ident $key:tt : $a:tt $b:tt $(, $($rest:tt)*)?) => {
+        $object.insert($crate::fql!(@key $key), $crate::fql!(@value $a $b));
+        $crate::fql!(@insert $object $($($rest)*)?);
+    };
+
+    (@bindings [$($out:expr,)*]) => (vec![$($out,)*]);
+    (@bindings [$($out:expr,)*] $key:tt : $a:tt $(, $($rest:tt)*)?) => (
+        $crate::fql!(
+            @bindings [$($out,)* $crate::query::basic::Binding::new(
+                $crate::fql!(@key $key),
+                $crate::fql!(@value $a),
+            ),]
+            $($($rest)*)?
+        )
+    );
+    (@bindings [$($out:expr,)*] $key:tt : $a:tt $b:tt $(, $($rest:tt)*)?) => (
+        $crate::fql!(
+            @bindings [$($out,)* $crate::query::basic::Binding::new(
+                $crate::fql!(@key $key),
+                $crate::fql!(@value $a $b),
+            ),]
+            $($($rest)*)?
+        )
+    );
+
+    (@key $key:ident) => (stringify!($key));
+    (@key $key:literal) => ($key);
+
+    // Splits the arguments of a call, grouping each in parentheses.
+    (@args $function:ident [$($out:tt)*]) => ($crate::fql!(@call $function $($out)*));
+    (@args $function:ident [$($out:tt)*] $a:tt $(, $($rest:tt)*)?) => (
+        $crate::fql!(@args $function [$($out)* ($a)] $($($rest)*)?)
+    );
+    (@args $function:ident [$($out:tt)*] $a:tt $b:tt $(, $($rest:tt)*)?) => (
+        $crate::fql!(@args $function [$($out)* ($a $b)] $($($rest)*)?)
+    );
+
+    // Paginate options and other optional parameters become builder calls.
+    (@options [$($query:tt)*] []) => ($($query)*);
+    (@options [$($query:tt)*] [$($out:tt)*]) => ($crate::fql!(@with [$($query)*] $($out)*));
+    (@options [$($query:tt)*] [$($out:tt)*] ts : $($rest:tt)*) => (
+        $crate::fql!(@options [$($query)*] [$($out)*] timestamp : $($rest)*)
+    );
+    (@options [$($query:tt)*] [$($out:tt)*] $key:ident : $a:tt $(, $($rest:tt)*)?) => (
+        $crate::fql!(
+            @options [$($query)*] [$($out)* $key ($crate::fql!(@value $a))]
+            $($($rest)*)?
+        )
+    );
+    (@options [$($query:tt)*] [$($out:tt)*] $key:ident : $a:tt $b:tt $(, $($rest:tt)*)?) => (
+        $crate::fql!(
+            @options [$($query)*] [$($out)* $key ($crate::fql!(@value $a $b))]
+            $($($rest)*)?
+        )
+    );
+
+    (@with [$($query:tt)*] $($method:ident ($arg:expr))+) => ({
+        let mut query = $($query)*;
+        $(query.$method($arg);)+
+        query
+    });
+
+    (@new $($path:ident)::+ $(($($a:tt)*))*) => (
+        $crate::query::$($path)::+::new($($crate::fql!(@value $($a)*)),*)
+    );
+    (@from $($path:ident)::+ $(($($a:tt)*))+) => (
+        $crate::query::$($path)::+::from(vec![$($crate::expr::Expr::from($crate::fql!(@value $($a)*))),+])
+    );
+    (@variadic $($path:ident)::+ ($($a:tt)*)) => ($crate::fql!(@new $($path)::+ ($($a)*)));
+    (@variadic $($path:ident)::+ $(($($a:tt)*))+) => (
+        $crate::query::$($path)::+::new($crate::fql!(@value [$($($a)*),+]))
+    );
+    (@push $($path:ident)::+ [$($a:tt)*] $(($($b:tt)*))+) => (
+        $crate::fql!(
+            @with [$crate::fql!(@new $($path)::+ $($a)*)]
+            $(push ($crate::fql!(@value $($b)*)))+
+        )
+    );
+
+    (@unit ("second")) => ($crate::query::datetime::EpochUnit::Second);
+    (@unit ("millisecond")) => ($crate::query::datetime::EpochUnit::Millisecond);
+    (@unit ("microsecond")) => ($crate::query::datetime::EpochUnit::Microsecond);
+    (@unit ("nanosecond")) => ($crate::query::datetime::EpochUnit::Nanosecond);
+    (@unit ($($a:tt)*)) => ($crate::fql!(@value $($a)*));
+
+    (@normalizer ("NFKCCaseFold")) => ($crate::query::string::Normalizer::NFKCCaseFold);
+    (@normalizer ("NFC")) => ($crate::query::string::Normalizer::NFC);
+    (@normalizer ("NFD")) => ($crate::query::string::Normalizer::NFD);
+    (@normalizer ("NFKC")) => ($crate::query::string::Normalizer::NFKC);
+    (@normalizer ("NFKD")) => ($crate::query::string::Normalizer::NFKD);
+    (@normalizer ($($a:tt)*)) => ($crate::fql!(@value $($a)*));
+
+    (@action ("create")) => ($crate::query::write::Action::Create);
+    (@action ("delete")) => ($crate::query::write::Action::Delete);
+    (@action ("update")) => ($crate::query::write::Action::Update);
+    (@action ($($a:tt)*)) => ($crate::fql!(@value $($a)*));
+
+    (@path ([$($a:tt)*])) => ($crate::fql!(@array [] $($a)*));
+    (@path ($($a:tt)*)) => ($crate::fql!(@value $($a)*));
+
+    (@call Ref (Class ($($a:tt)*)) ($($b:tt)*)) => (
+        $crate::fql!(
+            @with [$crate::expr::Ref::instance($crate::fql!(@value $($b)*))]
+            set_class ($crate::fql!(@value $($a)*))
+        )
+    );
+    (@call Query ($($a:tt)*)) => ($crate::expr::Expr::from($crate::fql!(@value $($a)*)).into_quoted());
+
+    (@call HasIdentity) => ($crate::fql!(@new auth::HasIdentity));
+    (@call Identify $($a:tt)*) => ($crate::fql!(@new auth::Identify $($a)*));
+    (@call Identity) => ($crate::fql!(@new auth::Identity));
+    (@call Login $a:tt ({ password : $($b:tt)+ })) => ($crate::fql!(@new auth::Login $a ($($b)+)));
+    (@call Logout $a:tt) => ($crate::fql!(@new auth::Logout $a));
+
+    (@call At $a:tt $b:tt) => ($crate::fql!(@new basic::At $a $b));
+    (@call Call (Function ($($a:tt)*)) ($($b:tt)*)) => (
+        $crate::query::basic::Call::new(
+            $crate::expr::Ref::function($crate::fql!(@value $($a)*)),
+            $crate::fql!(@value $($b)*),
+        )
+    );
+    (@call Call $a:tt $b:tt) => ($crate::fql!(@new basic::Call $a $b));
+    (@call Do $a:tt) => ($crate::fql!(@new basic::Do $a));
+    (@call Do $a:tt $($b:tt)+) => ($crate::fql!(@push basic::Do [$a] $($b)+));
+    (@call If ($($a:tt)*) ($($b:tt)*) ($($c:tt)*)) => (
+        $crate::query::basic::If::cond(
+            $crate::fql!(@value $($a)*),
+            $crate::fql!(@value $($b)*),
+            $crate::fql!(@value $($c)*),
+        )
+    );
+    (@call Lambda $a:tt $b:tt) => ($crate::fql!(@new basic::Lambda $a $b));
+    (@call Let ({ $($a:tt)* }) ($($b:tt)*)) => (
+        $crate::query::basic::Let::bindings(
+            $crate::fql!(@bindings [] $($a)*),
+            $crate::fql!(@value $($b)*),
+        )
+    );
+    (@call Var $a:tt) => ($crate::fql!(@new basic::Var $a));
+
+    (@call Append $a:tt $b:tt) => ($crate::fql!(@new collection::Append $a $b));
+    (@call Drop $a:tt $b:tt) => ($crate::fql!(@new collection::Drop $a $b));
+    (@call Filter $a:tt $b:tt) => ($crate::fql!(@new collection::Filter $b $a));
+    (@call Foreach $a:tt $b:tt) => ($crate::fql!(@new collection::Foreach $a $b));
+    (@call IsEmpty $a:tt) => ($crate::fql!(@new collection::IsEmpty $a));
+    (@call IsNonEmpty $a:tt) => ($crate::fql!(@new collection::IsNonEmpty $a));
+    (@call Map $a:tt $b:tt) => ($crate::fql!(@new collection::Map $a $b));
+    (@call Prepend $a:tt $b:tt) => ($crate::fql!(@new collection::Prepend $a $b));
+    (@call Take $a:tt $b:tt) => ($crate::fql!(@new collection::Take $a $b));
+
+    (@call ToDate $a:tt) => ($crate::fql!(@new conversion::ToDate $a));
+    (@call ToNumber $a:tt) => ($crate::fql!(@new conversion::ToNumber $a));
+    (@call ToString $a:tt) => ($crate::fql!(@new conversion::ToString $a));
+    (@call ToTime $a:tt) => ($crate::fql!(@new conversion::ToTime $a));
+
+    (@call Date $a:tt) => ($crate::fql!(@new datetime::Date $a));
+    (@call Epoch ($($a:tt)*) $b:tt) => (
+        $crate::query::datetime::Epoch::new($crate::fql!(@value $($a)*), $crate::fql!(@unit $b))
+    );
+    (@call Time $a:tt) => ($crate::fql!(@new datetime::Time $a));
+
+    (@call And $a:tt $($b:tt)+) => ($crate::fql!(@from logical::And $a $($b)+));
+    (@call Contains $a:tt ($($b:tt)*)) => (
+        $crate::query::logical::Contains::new($crate::fql!(@path $a), $crate::fql!(@value $($b)*))
+    );
+    (@call Equals $a:tt $($b:tt)+) => ($crate::fql!(@from logical::Equals $a $($b)+));
+    (@call Exists $a:tt) => ($crate::fql!(@new logical::Exists $a));
+    (@call Exists $a:tt ($($b:tt)*)) => (
+        $crate::fql!(@with [$crate::fql!(@new logical::Exists $a)] timestamp ($crate::fql!(@value $($b)*)))
+    );
+    (@call GT $a:tt $($b:tt)+) => ($crate::fql!(@from logical::Gt $a $($b)+));
+    (@call GTE $a:tt $($b:tt)+) => ($crate::fql!(@from logical::Gte $a $($b)+));
+    (@call LT $a:tt $($b:tt)+) => ($crate::fql!(@from logical::Lt $a $($b)+));
+    (@call LTE $a:tt $($b:tt)+) => ($crate::fql!(@from logical::Lte $a $($b)+));
+    (@call Not $a:tt) => ($crate::fql!(@new logical::Not $a));
+    (@call Or $a:tt $($b:tt)+) => ($crate::fql!(@from logical::Or $a $($b)+));
+
+    (@call Abs $a:tt) => ($crate::fql!(@new math::Abs $a));
+    (@call Acos $a:tt) => ($crate::fql!(@new math::Acos $a));
+    (@call Add $($a:tt)+) => ($crate::fql!(@variadic math::Add $($a)+));
+    (@call Asin $a:tt) => ($crate::fql!(@new math::Asin $a));
+    (@call Atan $a:tt) => ($crate::fql!(@new math::Atan $a));
+    (@call BitAnd $($a:tt)+) => ($crate::fql!(@variadic math::BitAnd $($a)+));
+    (@call BitNot $a:tt) => ($crate::fql!(@new math::BitNot $a));
+    (@call BitOr $($a:tt)+) => ($crate::fql!(@variadic math::BitOr $($a)+));
+    (@call BitXor $($a:tt)+) => ($crate::fql!(@variadic math::BitXor $($a)+));
+    (@call Ceil $a:tt) => ($crate::fql!(@new math::Ceil $a));
+    (@call Cos $a:tt) => ($crate::fql!(@new math::Cos $a));
+    (@call Cosh $a:tt) => ($crate::fql!(@new math::Cosh $a));
+    (@call Degrees $a:tt) => ($crate::fql!(@new math::Degrees $a));
+    (@call Divide $($a:tt)+) => ($crate::fql!(@variadic math::Divide $($a)+));
+    (@call Exp $a:tt) => ($crate::fql!(@new math::Exp $a));
+    (@call Floor $a:tt) => ($crate::fql!(@new math::Floor $a));
+    (@call Hypot ($($a:tt)*)) => ($crate::query::math::Hypot::isosceles($crate::fql!(@value $($a)*)));
+    (@call Hypot $a:tt $b:tt) => ($crate::fql!(@new math::Hypot $a $b));
+    (@call Ln $a:tt) => ($crate::fql!(@new math::Ln $a));
+    (@call Log $a:tt) => ($crate::fql!(@new math::Log $a));
+    (@call Max $($a:tt)+) => ($crate::fql!(@variadic math::Max $($a)+));
+    (@call Min $($a:tt)+) => ($crate::fql!(@variadic math::Min $($a)+));
+    (@call Modulo $($a:tt)+) => ($crate::fql!(@variadic math::Modulo $($a)+));
+    (@call Multiply $($a:tt)+) => ($crate::fql!(@variadic math::Multiply $($a)+));
+    (@call Pow $a:tt $b:tt) => ($crate::fql!(@new math::Pow $a $b));
+    (@call Radians $a:tt) => ($crate::fql!(@new math::Radians $a));
+    (@call Round $a:tt) => ($crate::fql!(@new math::Round $a));
+    (@call Round $a:tt ($($b:tt)*)) => (
+        $crate::fql!(@with [$crate::fql!(@new math::Round $a)] precision ($crate::fql!(@value $($b)*)))
+    );
+    (@call Sign $a:tt) => ($crate::fql!(@new math::Sign $a));
+    (@call Sin $a:tt) => ($crate::fql!(@new math::Sin $a));
+    (@call Sinh $a:tt) => ($crate::fql!(@new math::Sinh $a));
+    (@call Sqrt $a:tt) => ($crate::fql!(@new math::Sqrt $a));
+    (@call Subtract $($a:tt)+) => ($crate::fql!(@variadic math::Subtract $($a)+));
+    (@call Tan $a:tt) => ($crate::fql!(@new math::Tan $a));
+    (@call Tanh $a:tt) => ($crate::fql!(@new math::Tanh $a));
+    (@call Trunc $a:tt) => ($crate::fql!(@new math::Trunc $a));
+    (@call Trunc $a:tt ($($b:tt)*)) => (
+        $crate::fql!(@with [$crate::fql!(@new math::Trunc $a)] precision ($crate::fql!(@value $($b)*)))
+    );
+
+    (@call Abort $a:tt) => ($crate::fql!(@new misc::Abort $a));
+    (@call Class ($($a:tt)*)) => ($crate::query::misc::Class::find($crate::fql!(@value $($a)*)));
+    (@call Class ($($a:tt)*) ($($b:tt)*)) => (
+        $crate::fql!(
+            @with [$crate::query::misc::Class::find($crate::fql!(@value $($a)*))]
+            scope ($crate::fql!(@value $($b)*))
+        )
+    );
+    (@call Classes) => ($crate::query::misc::Classes::all());
+    (@call Classes ($($a:tt)*)) => (
+        $crate::query::misc::Classes::from_database($crate::fql!(@value $($a)*))
+    );
+    (@call Database ($($a:tt)*)) => ($crate::query::misc::Database::find($crate::fql!(@value $($a)*)));
+    (@call Database ($($a:tt)*) ($($b:tt)*)) => (
+        $crate::fql!(
+            @with [$crate::query::misc::Database::find($crate::fql!(@value $($a)*))]
+            scope ($crate::fql!(@value $($b)*))
+        )
+    );
+    (@call Databases) => ($crate::query::misc::Databases::all());
+    (@call Databases ($($a:tt)*)) => (
+        $crate::query::misc::Databases::from_database($crate::fql!(@value $($a)*))
+    );
+    (@call Function ($($a:tt)*)) => ($crate::query::misc::Function::find($crate::fql!(@value $($a)*)));
+    (@call Function ($($a:tt)*) ($($b:tt)*)) => (
+        $crate::fql!(
+            @with [$crate::query::misc::Function::find($crate::fql!(@value $($a)*))]
+            scope ($crate::fql!(@value $($b)*))
+        )
+    );
+    (@call Functions) => ($crate::query::misc::Functions::all());
+    (@call Functions ($($a:tt)*)) => (
+        $crate::query::misc::Functions::from_database($crate::fql!(@value $($a)*))
+    );
+    (@call Index ($($a:tt)*)) => ($crate::query::misc::Index::find($crate::fql!(@value $($a)*)));
+    (@call Index ($($a:tt)*) ($($b:tt)*)) => (
+        $crate::fql!(
+            @with [$crate::query::misc::Index::find($crate::fql!(@value $($a)*))]
+            scope ($crate::fql!(@value $($b)*))
+        )
+    );
+    (@call Indexes) => ($crate::query::misc::Indexes::all());
+    (@call Indexes ($($a:tt)*)) => (
+        $crate::query::misc::Indexes::from_database($crate::fql!(@value $($a)*))
+    );
+    (@call NewId) => ($crate::fql!(@new misc::NewId));
+
+    (@call Get ($($a:tt)*)) => ($crate::query::read::Get::instance($crate::fql!(@value $($a)*)));
+    (@call Get ($($a:tt)*) ($($b:tt)*)) => (
+        $crate::fql!(
+            @with [$crate::query::read::Get::instance($crate::fql!(@value $($a)*))]
+            timestamp ($crate::fql!(@value $($b)*))
+        )
+    );
+    (@call KeyFromSecret $a:tt) => ($crate::fql!(@new read::KeyFromSecret $a));
+    (@call Paginate $a:tt) => ($crate::fql!(@new read::Paginate $a));
+    (@call Paginate $a:tt ({ $($b:tt)* })) => (
+        $crate::fql!(@options [$crate::fql!(@new read::Paginate $a)] [] $($b)*)
+    );
+    (@call Select $a:tt $b:tt) => ($crate::fql!(@new read::Select $a $b));
+    (@call Select $a:tt $b:tt ($($c:tt)*)) => (
+        $crate::fql!(@with [$crate::fql!(@new read::Select $a $b)] default ($crate::fql!(@value $($c)*)))
+    );
+    (@call SelectAll $a:tt $b:tt) => ($crate::fql!(@new read::SelectAll $a $b));
+
+    (@call Difference $a:tt $b:tt) => ($crate::fql!(@new set::Difference $a $b));
+    (@call Difference $a:tt $b:tt $($c:tt)+) => ($crate::fql!(@push set::Difference [$a $b] $($c)+));
+    (@call Distinct $a:tt) => ($crate::fql!(@new set::Distinct $a));
+    (@call Intersection $a:tt $b:tt) => ($crate::fql!(@new set::Intersection $a $b));
+    (@call Intersection $a:tt $b:tt $($c:tt)+) => ($crate::fql!(@push set::Intersection [$a $b] $($c)+));
+    (@call Join $a:tt $b:tt) => ($crate::fql!(@new set::Join $a $b));
+    (@call Match $a:tt) => ($crate::fql!(@new set::Match $a));
+    (@call Match $a:tt ($($b:tt)*)) => (
+        $crate::fql!(@new set::Match $a).with_terms($crate::fql!(@value $($b)*))
+    );
+    (@call Union $a:tt $b:tt) => ($crate::fql!(@new set::Union $a $b));
+    (@call Union $a:tt $b:tt $($c:tt)+) => ($crate::fql!(@push set::Union [$a $b] $($c)+));
+
+    (@call Casefold $a:tt) => ($crate::fql!(@new string::CaseFold $a));
+    (@call Casefold $a:tt $b:tt) => (
+        $crate::fql!(@with [$crate::fql!(@new string::CaseFold $a)] normalizer ($crate::fql!(@normalizer $b)))
+    );
+    (@call Concat $a:tt) => ($crate::fql!(@new string::Concat $a ("")));
+    (@call Concat $a:tt $b:tt) => ($crate::fql!(@new string::Concat $a $b));
+    (@call FindStr $a:tt $b:tt) => ($crate::fql!(@new string::FindStr $a $b));
+    (@call FindStr $a:tt $b:tt ($($c:tt)*)) => (
+        $crate::fql!(@with [$crate::fql!(@new string::FindStr $a $b)] start ($crate::fql!(@value $($c)*)))
+    );
+    (@call FindStrRegex $a:tt $b:tt) => ($crate::fql!(@new string::FindStrRegex $a $b));
+    (@call FindStrRegex $a:tt $b:tt ($($c:tt)*)) => (
+        $crate::fql!(@with [$crate::fql!(@new string::FindStrRegex $a $b)] start ($crate::fql!(@value $($c)*)))
+    );
+    (@call FindStrRegex $a:tt $b:tt ($($c:tt)*) ($($d:tt)*)) => (
+        $crate::fql!(
+            @with [$crate::fql!(@new string::FindStrRegex $a $b)]
+            start ($crate::fql!(@value $($c)*))
+            num_results ($crate::fql!(@value $($d)*))
+        )
+    );
+    (@call LTrim $a:tt) => ($crate::fql!(@new string::LTrim $a));
+    (@call Length $a:tt) => ($crate::fql!(@new string::Length $a));
+    (@call LowerCase $a:tt) => ($crate::fql!(@new string::LowerCase $a));
+    (@call RTrim $a:tt) => ($crate::fql!(@new string::RTrim $a));
+    (@call Repeat $a:tt $b:tt) => ($crate::fql!(@new string::Repeat $a $b));
+    (@call ReplaceStr $a:tt $b:tt $c:tt) => ($crate::fql!(@new string::ReplaceStr $a $b $c));
+    (@call ReplaceStrRegex $a:tt $b:tt $c:tt) => (
+        $crate::fql!(@new string::ReplaceStrRegex $a $b $c (false))
+    );
+    (@call ReplaceStrRegex $a:tt $b:tt $c:tt $d:tt) => (
+        $crate::fql!(@new string::ReplaceStrRegex $a $b $c $d)
+    );
+    (@call Space $a:tt) => ($crate::fql!(@new string::Space $a));
+    (@call SubString $a:tt $b:tt) => ($crate::fql!(@new string::SubString $a $b));
+    (@call SubString $a:tt $b:tt ($($c:tt)*)) => (
+        $crate::fql!(@with [$crate::fql!(@new string::SubString $a $b)] length ($crate::fql!(@value $($c)*)))
+    );
+    (@call TitleCase $a:tt) => ($crate::fql!(@new string::TitleCase $a));
+    (@call Trim $a:tt) => ($crate::fql!(@new string::Trim $a));
+    (@call UpperCase $a:tt) => ($crate::fql!(@new string::UpperCase $a));
+
+    (@call Create $a:tt ({ data : $b:tt $(, $($c:tt)*)? })) => (
+        $crate::fql!(@options [$crate::fql!(@new write::Create $a ($b))] [] $($($c)*)?)
+    );
+    (@call Create $a:tt ({ data : $b:tt $b2:tt $(, $($c:tt)*)? })) => (
+        $crate::fql!(@options [$crate::fql!(@new write::Create $a ($b $b2))] [] $($($c)*)?)
+    );
+    (@call CreateClass $a:tt) => ($crate::fql!(@new write::CreateClass $a));
+    (@call CreateDatabase $a:tt) => ($crate::fql!(@new write::CreateDatabase $a));
+    (@call CreateFunction $a:tt) => ($crate::fql!(@new write::CreateFunction $a));
+    (@call CreateIndex $a:tt) => ($crate::fql!(@new write::CreateIndex $a));
+    (@call CreateKey $a:tt) => ($crate::fql!(@new write::CreateKey $a));
+    (@call Delete $a:tt) => ($crate::fql!(@new write::Delete $a));
+    (@call Insert ($($a:tt)*) ($($b:tt)*) $c:tt ($($d:tt)*)) => (
+        $crate::query::write::Insert::new(
+            $crate::fql!(@value $($a)*),
+            $crate::fql!(@value $($b)*),
+            $crate::fql!(@action $c),
+            $crate::fql!(@value $($d)*),
+        )
+    );
+    (@call Remove ($($a:tt)*) ($($b:tt)*) $c:tt) => (
+        $crate::query::write::Remove::new(
+            $crate::fql!(@value $($a)*),
+            $crate::fql!(@value $($b)*),
+            $crate::fql!(@action $c),
+        )
+    );
+    (@call Replace $a:tt $b:tt) => ($crate::fql!(@new write::Replace $a $b));
+    (@call Update $a:tt $b:tt) => ($crate::fql!(@new write::Update $a $b));
+
+    (@call $function:ident $($args:tt)*) => (
+        compile_error!(concat!(
+            "unknown function or unsupported arguments: `",
+            stringify!($function),
+            "`"
+        ))
+    );
+
+    ($($tt:tt)+) => ($crate::expr::Expr::from($crate::fql!(@value $($tt)+)));
+}
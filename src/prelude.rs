@@ -1,5 +1,6 @@
 pub use crate::{
     client::*,
+    document::FaunaDocument,
     expr::*,
     query::{
         auth::*, basic::*, collection::*, conversion::*, datetime::*, logical::*, math::*, misc::*,
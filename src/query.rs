@@ -12,7 +12,135 @@ pub mod set;
 pub mod string;
 pub mod write;
 
-#[derive(Debug, Clone, Serialize)]
+use crate::fql::find_function;
+use serde::{de, Deserialize, Deserializer};
+
+into_owned!(
+    enum Query {
+        Difference,
+        Distinct,
+        Intersection,
+        Join,
+        Match,
+        Union,
+        Date,
+        Epoch,
+        Time,
+        CaseFold,
+        Concat,
+        FindStr,
+        FindStrRegex,
+        LTrim,
+        Length,
+        LowerCase,
+        RTrim,
+        Repeat,
+        ReplaceStr,
+        ReplaceStrRegex,
+        Space,
+        SubString,
+        TitleCase,
+        Trim,
+        UpperCase,
+        HasIdentity,
+        Identify,
+        Identity,
+        Login,
+        Logout,
+        ToDate,
+        ToNumber,
+        ToString,
+        ToTime,
+        At,
+        Call,
+        Do,
+        Let,
+        Var,
+        Lambda,
+        If,
+        Append,
+        Drop,
+        Filter,
+        Foreach,
+        IsEmpty,
+        IsNonEmpty,
+        Map,
+        Prepend,
+        Take,
+        And,
+        Or,
+        Not,
+        Lt,
+        Lte,
+        Gt,
+        Gte,
+        Contains,
+        Equals,
+        Exists,
+        Abs,
+        Acos,
+        Add,
+        Asin,
+        Atan,
+        BitAnd,
+        BitNot,
+        BitOr,
+        BitXor,
+        Ceil,
+        Cos,
+        Cosh,
+        Degrees,
+        Divide,
+        Exp,
+        Floor,
+        Hypot,
+        Ln,
+        Log,
+        Max,
+        Min,
+        Modulo,
+        Multiply,
+        Pow,
+        Radians,
+        Round,
+        Sign,
+        Sin,
+        Sinh,
+        Sqrt,
+        Subtract,
+        Tan,
+        Tanh,
+        Trunc,
+        CreateClass,
+        CreateDatabase,
+        CreateIndex,
+        CreateFunction,
+        CreateKey,
+        Create,
+        Insert,
+        Delete,
+        Remove,
+        Replace,
+        Update,
+        Get,
+        KeyFromSecret,
+        Paginate,
+        Select,
+        SelectAll,
+        Abort,
+        Class,
+        Classes,
+        Database,
+        Databases,
+        Function,
+        Functions,
+        Index,
+        Indexes,
+        NewId,
+    }
+);
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum Query<'a> {
     Difference(set::Difference<'a>),
@@ -147,3 +275,265 @@ pub enum Query<'a> {
     Indexes(misc::Indexes<'a>),
     NewId(misc::NewId<'a>),
 }
+
+/// Reads a query in the wire format, the function chosen by the keys of the
+/// object. See the `Deserialize` implementation of `Expr` for reading any
+/// expression.
+impl<'de, 'a> Deserialize<'de> for Query<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let json = serde_json::Value::deserialize(deserializer)?;
+        Query::from_json(&json).map_err(de::Error::custom)
+    }
+}
+
+impl<'a> Query<'a> {
+    pub(crate) fn from_json(json: &serde_json::Value) -> Result<Self, serde_json::Error> {
+        let function = match json {
+            serde_json::Value::Object(obj) => find_function(obj.keys().map(String::as_str)),
+            _ => None,
+        };
+
+        let function = match function {
+            Some(function) => function,
+            None => return Err(de::Error::custom(format!("not a known function: {}", json))),
+        };
+
+        let query = match function.key {
+            "difference" => Query::Difference(Deserialize::deserialize(json)?),
+            "distinct" => Query::Distinct(Deserialize::deserialize(json)?),
+            "intersection" => Query::Intersection(Deserialize::deserialize(json)?),
+            "join" => Query::Join(Deserialize::deserialize(json)?),
+            "match" => Query::Match(Deserialize::deserialize(json)?),
+            "union" => Query::Union(Deserialize::deserialize(json)?),
+            "date" => Query::Date(Deserialize::deserialize(json)?),
+            "epoch" => Query::Epoch(Deserialize::deserialize(json)?),
+            "time" => Query::Time(Deserialize::deserialize(json)?),
+            "casefold" => Query::CaseFold(Deserialize::deserialize(json)?),
+            "concat" => Query::Concat(Deserialize::deserialize(json)?),
+            "findstr" => Query::FindStr(Deserialize::deserialize(json)?),
+            "findstrregex" => Query::FindStrRegex(Deserialize::deserialize(json)?),
+            "ltrim" => Query::LTrim(Deserialize::deserialize(json)?),
+            "length" => Query::Length(Deserialize::deserialize(json)?),
+            "lowercase" => Query::LowerCase(Deserialize::deserialize(json)?),
+            "rtrim" => Query::RTrim(Deserialize::deserialize(json)?),
+            "repeat" => Query::Repeat(Deserialize::deserialize(json)?),
+            "replacestr" => Query::ReplaceStr(Deserialize::deserialize(json)?),
+            "replacestrregex" => Query::ReplaceStrRegex(Deserialize::deserialize(json)?),
+            "space" => Query::Space(Deserialize::deserialize(json)?),
+            "substring" => Query::SubString(Deserialize::deserialize(json)?),
+            "titlecase" => Query::TitleCase(Deserialize::deserialize(json)?),
+            "trim" => Query::Trim(Deserialize::deserialize(json)?),
+            "uppercase" => Query::UpperCase(Deserialize::deserialize(json)?),
+            "has_identity" => Query::HasIdentity(Deserialize::deserialize(json)?),
+            "identify" => Query::Identify(Deserialize::deserialize(json)?),
+            "identity" => Query::Identity(Deserialize::deserialize(json)?),
+            "login" => Query::Login(Deserialize::deserialize(json)?),
+            "logout" => Query::Logout(Deserialize::deserialize(json)?),
+            "to_date" => Query::ToDate(Deserialize::deserialize(json)?),
+            "to_number" => Query::ToNumber(Deserialize::deserialize(json)?),
+            "to_string" => Query::ToString(Deserialize::deserialize(json)?),
+            "to_time" => Query::ToTime(Deserialize::deserialize(json)?),
+            "at" => Query::At(Deserialize::deserialize(json)?),
+            "call" => Query::Call(Deserialize::deserialize(json)?),
+            "do" => Query::Do(Deserialize::deserialize(json)?),
+            "let" => Query::Let(Deserialize::deserialize(json)?),
+            "var" => Query::Var(Deserialize::deserialize(json)?),
+            "lambda" => Query::Lambda(Deserialize::deserialize(json)?),
+            "if" => Query::If(Deserialize::deserialize(json)?),
+            "append" => Query::Append(Deserialize::deserialize(json)?),
+            "drop" => Query::Drop(Deserialize::deserialize(json)?),
+            "filter" => Query::Filter(Deserialize::deserialize(json)?),
+            "foreach" => Query::Foreach(Deserialize::deserialize(json)?),
+            "is_empty" => Query::IsEmpty(Deserialize::deserialize(json)?),
+            "is_nonempty" => Query::IsNonEmpty(Deserialize::deserialize(json)?),
+            "map" => Query::Map(Deserialize::deserialize(json)?),
+            "prepend" => Query::Prepend(Deserialize::deserialize(json)?),
+            "take" => Query::Take(Deserialize::deserialize(json)?),
+            "and" => Query::And(Deserialize::deserialize(json)?),
+            "or" => Query::Or(Deserialize::deserialize(json)?),
+            "not" => Query::Not(Deserialize::deserialize(json)?),
+            "lt" => Query::Lt(Deserialize::deserialize(json)?),
+            "lte" => Query::Lte(Deserialize::deserialize(json)?),
+            "gt" => Query::Gt(Deserialize::deserialize(json)?),
+            "gte" => Query::Gte(Deserialize::deserialize(json)?),
+            "contains" => Query::Contains(Deserialize::deserialize(json)?),
+            "equals" => Query::Equals(Deserialize::deserialize(json)?),
+            "exists" => Query::Exists(Deserialize::deserialize(json)?),
+            "abs" => Query::Abs(Deserialize::deserialize(json)?),
+            "acos" => Query::Acos(Deserialize::deserialize(json)?),
+            "add" => Query::Add(Deserialize::deserialize(json)?),
+            "asin" => Query::Asin(Deserialize::deserialize(json)?),
+            "atan" => Query::Atan(Deserialize::deserialize(json)?),
+            "bitand" => Query::BitAnd(Deserialize::deserialize(json)?),
+            "bitnot" => Query::BitNot(Deserialize::deserialize(json)?),
+            "bitor" => Query::BitOr(Deserialize::deserialize(json)?),
+            "bitxor" => Query::BitXor(Deserialize::deserialize(json)?),
+            "ceil" => Query::Ceil(Deserialize::deserialize(json)?),
+            "cos" => Query::Cos(Deserialize::deserialize(json)?),
+            "cosh" => Query::Cosh(Deserialize::deserialize(json)?),
+            "degrees" => Query::Degrees(Deserialize::deserialize(json)?),
+            "divide" => Query::Divide(Deserialize::deserialize(json)?),
+            "exp" => Query::Exp(Deserialize::deserialize(json)?),
+            "floor" => Query::Floor(Deserialize::deserialize(json)?),
+            "hypot" => Query::Hypot(Deserialize::deserialize(json)?),
+            "ln" => Query::Ln(Deserialize::deserialize(json)?),
+            "log" => Query::Log(Deserialize::deserialize(json)?),
+            "max" => Query::Max(Deserialize::deserialize(json)?),
+            "min" => Query::Min(Deserialize::deserialize(json)?),
+            "modulo" => Query::Modulo(Deserialize::deserialize(json)?),
+            "multiply" => Query::Multiply(Deserialize::deserialize(json)?),
+            "pow" => Query::Pow(Deserialize::deserialize(json)?),
+            "radians" => Query::Radians(Deserialize::deserialize(json)?),
+            "round" => Query::Round(Deserialize::deserialize(json)?),
+            "sign" => Query::Sign(Deserialize::deserialize(json)?),
+            "sin" => Query::Sin(Deserialize::deserialize(json)?),
+            "sinh" => Query::Sinh(Deserialize::deserialize(json)?),
+            "sqrt" => Query::Sqrt(Deserialize::deserialize(json)?),
+            "subtract" => Query::Subtract(Deserialize::deserialize(json)?),
+            "tan" => Query::Tan(Deserialize::deserialize(json)?),
+            "tanh" => Query::Tanh(Deserialize::deserialize(json)?),
+            "trunc" => Query::Trunc(Deserialize::deserialize(json)?),
+            "create_class" => Query::CreateClass(Box::new(Deserialize::deserialize(json)?)),
+            "create_database" => Query::CreateDatabase(Deserialize::deserialize(json)?),
+            "create_index" => Query::CreateIndex(Box::new(Deserialize::deserialize(json)?)),
+            "create_function" => Query::CreateFunction(Box::new(Deserialize::deserialize(json)?)),
+            "create_key" => Query::CreateKey(Box::new(Deserialize::deserialize(json)?)),
+            "create" => Query::Create(Deserialize::deserialize(json)?),
+            "insert" => Query::Insert(Box::new(Deserialize::deserialize(json)?)),
+            "delete" => Query::Delete(Deserialize::deserialize(json)?),
+            "remove" => Query::Remove(Deserialize::deserialize(json)?),
+            "replace" => Query::Replace(Deserialize::deserialize(json)?),
+            "update" => Query::Update(Deserialize::deserialize(json)?),
+            "get" => Query::Get(Deserialize::deserialize(json)?),
+            "key_from_secret" => Query::KeyFromSecret(Deserialize::deserialize(json)?),
+            "paginate" => Query::Paginate(Deserialize::deserialize(json)?),
+            "select" => Query::Select(Deserialize::deserialize(json)?),
+            "select_all" => Query::SelectAll(Deserialize::deserialize(json)?),
+            "abort" => Query::Abort(Deserialize::deserialize(json)?),
+            "class" => Query::Class(Deserialize::deserialize(json)?),
+            "classes" => Query::Classes(Deserialize::deserialize(json)?),
+            "database" => Query::Database(Deserialize::deserialize(json)?),
+            "databases" => Query::Databases(Deserialize::deserialize(json)?),
+            "function" => Query::Function(Deserialize::deserialize(json)?),
+            "functions" => Query::Functions(Deserialize::deserialize(json)?),
+            "index" => Query::Index(Deserialize::deserialize(json)?),
+            "indexes" => Query::Indexes(Deserialize::deserialize(json)?),
+            "new_id" => Query::NewId(Deserialize::deserialize(json)?),
+            key => return Err(de::Error::custom(format!("unsupported function `{}`", key))),
+        };
+
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Query;
+    use crate::{
+        fql::{self, find_function, FUNCTIONS},
+        prelude::*,
+    };
+    use chrono::{offset::TimeZone, Utc};
+    use serde_json::{self, json, Map};
+
+    /// A valid argument for the given key, a variable unless the field has a
+    /// more specific type.
+    fn argument(key: &str) -> serde_json::Value {
+        match key {
+            "action" => json!("create"),
+            "normalizer" => json!("NFKC"),
+            "unit" => json!("second"),
+            "var" => json!("x"),
+            "first" => json!(true),
+            "contains" | "select" | "select_all" => json!(["data", 0]),
+            "params" => json!({ "object": { "password": "x" } }),
+            _ => json!({ "var": "x" }),
+        }
+    }
+
+    /// The write functions take typed parameter objects, which are easier to
+    /// get right with the builders.
+    fn built(key: &str) -> Option<Query<'static>> {
+        let query = match key {
+            "create" => Query::from(Create::new(Ref::class("x"), Object::default())),
+            "create_class" => Query::from(CreateClass::new(ClassParams::new("x"))),
+            "create_database" => Query::from(CreateDatabase::new(DatabaseParams::new("x"))),
+            "create_function" => Query::from(CreateFunction::new(FunctionParams::new(
+                "x",
+                Lambda::new("x", Var::new("x")),
+            ))),
+            "create_index" => Query::from(CreateIndex::new(IndexParams::new("x", Ref::class("x")))),
+            "create_key" => Query::from(CreateKey::new(KeyParams::new(
+                Ref::database("x"),
+                Role::Admin,
+            ))),
+            "insert" => Query::from(Insert::new(
+                Ref::instance("x"),
+                Utc.timestamp_opt(0, 0).unwrap(),
+                Action::Create,
+                InsertParams::new(Object::default(), Expr::null(), Expr::null()),
+            )),
+            "update" => Query::from(Update::new(Ref::instance("x"), UpdateParams::new())),
+            _ => return None,
+        };
+
+        Some(query)
+    }
+
+    fn sample(function: &fql::Function) -> serde_json::Value {
+        if let Some(query) = built(function.key) {
+            return serde_json::to_value(&query).unwrap();
+        }
+
+        let json: Map<String, serde_json::Value> = function
+            .args
+            .iter()
+            .map(|&arg| {
+                let value = if arg == function.key && function.variadic {
+                    json!([argument(arg)])
+                } else {
+                    argument(arg)
+                };
+
+                (arg.to_string(), value)
+            })
+            .collect();
+
+        serde_json::Value::Object(json)
+    }
+
+    #[test]
+    fn test_from_json_covers_functions() {
+        let mut failures = Vec::new();
+
+        for function in FUNCTIONS {
+            let json = sample(function);
+
+            let query = match Query::from_json(&json) {
+                Ok(query) => query,
+                Err(e) => {
+                    failures.push(format!("{} failed to parse: {}", function.name, e));
+                    continue;
+                }
+            };
+
+            let serialized = serde_json::to_value(&query).unwrap();
+            let keys = serialized.as_object().unwrap().keys().map(String::as_str);
+
+            if find_function(keys).map(|f| f.key) != Some(function.key) {
+                failures.push(format!("{} serialized as {}", function.name, serialized));
+            }
+
+            for arg in function.args {
+                if json[arg] != serialized[arg] {
+                    failures.push(format!("{} lost the argument {}", function.name, arg));
+                }
+            }
+        }
+
+        assert!(failures.is_empty(), "{:#?}", failures);
+    }
+}
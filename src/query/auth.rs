@@ -6,13 +6,23 @@ use crate::{
 
 query![HasIdentity, Identify, Identity, Login, Logout];
 
+into_owned! {
+    HasIdentity { has_identity },
+    Identify { identify, password },
+    Identity { identity },
+    Login { login, params },
+    LoginObject { password },
+    LoginParams { object },
+    Logout { logout },
+}
+
 /// The `HasIdentity` function returns `true` if the current client
 /// authentication credentials have an associated identity, and `false` if they
 /// don’t.
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/authentication/hasidentity).
-#[derive(Serialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct HasIdentity<'a> {
     has_identity: Expr<'a>,
 }
@@ -29,7 +39,7 @@ impl<'a> HasIdentity<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/authentication/identify).
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Identify<'a> {
     identify: Expr<'a>,
     password: Expr<'a>,
@@ -50,7 +60,7 @@ impl<'a> Identify<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/authentication/identity).
-#[derive(Serialize, Clone, Debug, Default)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Identity<'a> {
     identity: Expr<'a>,
 }
@@ -66,19 +76,19 @@ impl<'a> Identity<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/authentication/login).
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Login<'a> {
     login: Expr<'a>,
     params: LoginParams<'a>,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct LoginObject<'a> {
     password: Expr<'a>,
 }
 
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct LoginParams<'a> {
     object: LoginObject<'a>,
@@ -102,7 +112,7 @@ impl<'a> Login<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/authentication/logout).
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Logout<'a> {
     logout: Expr<'a>,
 }
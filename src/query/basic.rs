@@ -1,14 +1,25 @@
 //! Basic functions
 use crate::{
-    expr::{Expr, Ref},
+    expr::{Array, Expr, Ref},
     query::Query,
 };
 use chrono::{DateTime, Utc};
-use std::{borrow::Cow, collections::BTreeMap};
+use std::{borrow::Cow, cell::Cell, collections::BTreeMap};
 
 // Implements From<fun> for Query
 query![At, Call, If, Do, Let, Var, Lambda];
 
+into_owned! {
+    At { timestamp, expression },
+    Call { call, arguments },
+    If { cond, if_true, if_false },
+    Do { queries },
+    Lambda { params, expr },
+    Let { bindings, in_expr },
+    Binding { 0, 1 },
+    Var { var },
+}
+
 /// The At function executes a temporal query, a query which examines the data
 /// in the past.
 ///
@@ -21,7 +32,7 @@ query![At, Call, If, Do, Let, Var, Lambda];
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/basic/at);
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct At<'a> {
     #[serde(rename = "at")]
     timestamp: Expr<'a>,
@@ -47,7 +58,7 @@ impl<'a> At<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/basic/call);
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Call<'a> {
     call: Expr<'a>,
     arguments: Expr<'a>,
@@ -72,7 +83,7 @@ impl<'a> Call<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/basic/if);
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct If<'a> {
     #[serde(rename = "if")]
     cond: Expr<'a>,
@@ -96,6 +107,21 @@ impl<'a> If<'a> {
             if_false: if_false.into(),
         }
     }
+
+    /// The condition choosing the branch.
+    pub fn condition(&self) -> &Expr<'a> {
+        &self.cond
+    }
+
+    /// The expression evaluated if the condition is `true`.
+    pub fn if_true(&self) -> &Expr<'a> {
+        &self.if_true
+    }
+
+    /// The expression evaluated if the condition is `false`.
+    pub fn if_false(&self) -> &Expr<'a> {
+        &self.if_false
+    }
 }
 
 /// The `Do` function evaluates a list of expressions which are provided as
@@ -114,7 +140,7 @@ impl<'a> If<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/basic/do).
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Do<'a> {
     #[serde(rename = "do")]
     queries: Vec<Expr<'a>>,
@@ -151,7 +177,7 @@ impl<'a> Do<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/basic/lambda).
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Lambda<'a> {
     #[serde(rename = "lambda")]
     params: Expr<'a>,
@@ -165,6 +191,71 @@ impl<'a> Lambda<'a> {
             expr: expr.into(),
         }
     }
+
+    /// A lambda of one parameter. The parameter is named after the nesting
+    /// depth, `$0` for the outermost one, and passed to the closure as a
+    /// `Var`.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// #
+    /// let lambda = Lambda::with(|cat| Select::new(vec!["data", "age"], Get::instance(cat)));
+    /// ```
+    pub fn with<F, E>(f: F) -> Self
+    where
+        F: FnOnce(Expr<'a>) -> E,
+        E: Into<Expr<'a>>,
+    {
+        with_names(1, |mut names| {
+            let name = names.remove(0);
+            let expr = f(Expr::from(Var::new(name.clone())));
+
+            Self::new(name, expr)
+        })
+    }
+
+    /// A lambda of two parameters with generated names.
+    pub fn with2<F, E>(f: F) -> Self
+    where
+        F: FnOnce(Expr<'a>, Expr<'a>) -> E,
+        E: Into<Expr<'a>>,
+    {
+        with_names(2, |names| {
+            let expr = f(
+                Expr::from(Var::new(names[0].clone())),
+                Expr::from(Var::new(names[1].clone())),
+            );
+
+            Self::new(Array::from(names), expr)
+        })
+    }
+
+    /// A lambda of three parameters with generated names.
+    pub fn with3<F, E>(f: F) -> Self
+    where
+        F: FnOnce(Expr<'a>, Expr<'a>, Expr<'a>) -> E,
+        E: Into<Expr<'a>>,
+    {
+        with_names(3, |names| {
+            let expr = f(
+                Expr::from(Var::new(names[0].clone())),
+                Expr::from(Var::new(names[1].clone())),
+                Expr::from(Var::new(names[2].clone())),
+            );
+
+            Self::new(Array::from(names), expr)
+        })
+    }
+
+    /// The name of the parameter, or an array of names.
+    pub fn params(&self) -> &Expr<'a> {
+        &self.params
+    }
+
+    /// The body of the function.
+    pub fn expr(&self) -> &Expr<'a> {
+        &self.expr
+    }
 }
 
 /// The `Let` function binds one or more variables to a single value or
@@ -178,7 +269,7 @@ impl<'a> Lambda<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/basic/let).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Let<'a> {
     #[serde(rename = "let")]
     bindings: BTreeMap<Cow<'a, str>, Expr<'a>>,
@@ -187,7 +278,7 @@ pub struct Let<'a> {
 }
 
 /// A single binding to be used in a `Let` query.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Binding<'a>(Cow<'a, str>, Expr<'a>);
 
 impl<'a> Binding<'a> {
@@ -216,6 +307,64 @@ impl<'a> Let<'a> {
 
         Self { bindings, in_expr }
     }
+
+    /// Binds the value to a variable named after the nesting depth, like
+    /// [Lambda::with](struct.Lambda.html#method.with), passed to the closure
+    /// as a `Var`.
+    ///
+    /// ```
+    /// # use faunadb::prelude::*;
+    /// #
+    /// let query = Let::with(Get::instance(Ref::instance("musti")), |cat| {
+    ///     If::cond(Contains::new(vec!["data", "hungry"], cat.clone()), cat, Expr::null())
+    /// });
+    /// ```
+    pub fn with<V, F, E>(value: V, f: F) -> Self
+    where
+        V: Into<Expr<'a>>,
+        F: FnOnce(Expr<'a>) -> E,
+        E: Into<Expr<'a>>,
+    {
+        with_names(1, |mut names| {
+            let name = names.remove(0);
+            let in_expr = f(Expr::from(Var::new(name.clone())));
+
+            Self::bindings(vec![Binding::new(name, value)], in_expr)
+        })
+    }
+
+    /// The bound variables with their values, ordered by name.
+    pub fn variables(&self) -> impl Iterator<Item = (&str, &Expr<'a>)> {
+        self.bindings.iter().map(|(name, expr)| (&**name, expr))
+    }
+
+    /// The expression the variables are available in.
+    pub fn in_expr(&self) -> &Expr<'a> {
+        &self.in_expr
+    }
+}
+
+thread_local! {
+    #[allow(clippy::missing_const_for_thread_local)]
+    static DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// Runs `f` with `count` variable names numbered from the current nesting
+/// depth of the `with` closures. The same structure always gets the same
+/// names, and nested lambdas and lets never shadow the outer variables.
+fn with_names<T>(count: usize, f: impl FnOnce(Vec<String>) -> T) -> T {
+    struct Restore(usize);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            DEPTH.with(|depth| depth.set(self.0));
+        }
+    }
+
+    let depth = DEPTH.with(|depth| depth.replace(depth.get() + count));
+    let _restore = Restore(depth);
+
+    f((depth..depth + count).map(|i| format!("${}", i)).collect())
 }
 
 /// Evaluate and return the value stored in a named variable.
@@ -225,7 +374,7 @@ impl<'a> Let<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/basic/var)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Var<'a> {
     var: Cow<'a, str>,
 }
@@ -237,6 +386,11 @@ impl<'a> Var<'a> {
     {
         Self { var: var.into() }
     }
+
+    /// The name of the variable.
+    pub fn name(&self) -> &str {
+        &self.var
+    }
 }
 
 #[cfg(test)]
@@ -327,6 +481,67 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_lambda_with() {
+        let lambda = || {
+            Lambda::with(|cat| {
+                Map::new(
+                    Select::new(vec!["data", "kittens"], cat.clone()),
+                    Lambda::with2(|kitten, age| Array::from(vec![cat, kitten, age])),
+                )
+            })
+        };
+
+        let serialized = serde_json::to_value(&Query::from(lambda())).unwrap();
+
+        let expected = json!({
+            "lambda": "$0",
+            "expr": {
+                "map": {
+                    "lambda": ["$1", "$2"],
+                    "expr": [{ "var": "$0" }, { "var": "$1" }, { "var": "$2" }]
+                },
+                "collection": { "select": ["data", "kittens"], "from": { "var": "$0" } }
+            }
+        });
+
+        assert_eq!(expected, serialized);
+        assert_eq!(lambda(), lambda());
+
+        let twice = || Lambda::with(|x| x);
+        assert_eq!(
+            serde_json::to_value(&Query::from(twice())).unwrap(),
+            serde_json::to_value(&Query::from(twice())).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_let_with() {
+        let let_var = Let::with(If::cond(true, "Musti", "Naukio"), |cat| {
+            Let::with(
+                Concat::new(Array::from(vec![cat.clone(), Expr::from("!")]), ""),
+                |name| Array::from(vec![cat, name]),
+            )
+        });
+
+        let serialized = serde_json::to_value(&Query::from(let_var)).unwrap();
+
+        assert_eq!(
+            json!({ "if": true, "then": "Musti", "else": "Naukio" }),
+            serialized["let"]["$0"]
+        );
+        assert_eq!(
+            json!([{ "var": "$0" }, { "var": "$1" }]),
+            serialized["in"]["in"]
+        );
+
+        let sibling = Let::with(1, |one| one);
+        assert_eq!(
+            json!({ "let": { "$0": 1 }, "in": { "var": "$0" } }),
+            serde_json::to_value(&Query::from(sibling)).unwrap()
+        );
+    }
+
     #[test]
     fn test_call() {
         let fun = Call::new(Ref::function("double"), 5);
@@ -349,4 +564,51 @@ mod tests {
 
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn test_deserialize_basic() {
+        let query: Query = serde_json::from_value(json!({
+            "lambda": ["cat", "age"],
+            "expr": {
+                "let": { "old": { "gt": [{ "var": "age" }, 10] } },
+                "in": { "if": { "var": "old" }, "then": { "var": "cat" }, "else": null }
+            }
+        }))
+        .unwrap();
+
+        let lambda = match query {
+            Query::Lambda(lambda) => lambda,
+            query => panic!("expected a lambda, got {:?}", query),
+        };
+
+        assert_eq!(
+            json!(["cat", "age"]),
+            serde_json::to_value(lambda.params()).unwrap()
+        );
+
+        let let_expr = match lambda.expr() {
+            Expr::Query(query) => match **query {
+                Query::Let(ref let_expr) => let_expr,
+                ref query => panic!("expected a let, got {:?}", query),
+            },
+            expr => panic!("expected a query, got {:?}", expr),
+        };
+
+        let variables: Vec<&str> = let_expr.variables().map(|(name, _)| name).collect();
+        assert_eq!(vec!["old"], variables);
+
+        match let_expr.in_expr() {
+            Expr::Query(query) => match **query {
+                Query::If(ref cond) => {
+                    assert_eq!(
+                        json!({ "var": "old" }),
+                        serde_json::to_value(cond.condition()).unwrap()
+                    );
+                    assert_eq!(json!(null), serde_json::to_value(cond.if_false()).unwrap());
+                }
+                ref query => panic!("expected an if, got {:?}", query),
+            },
+            expr => panic!("expected a query, got {:?}", expr),
+        }
+    }
 }
@@ -6,6 +6,18 @@ use crate::{
 
 query![Append, Drop, Filter, Foreach, IsEmpty, IsNonEmpty, Map, Prepend, Take];
 
+into_owned! {
+    Append { append, collection },
+    Drop { drop, collection },
+    Filter { filter, collection },
+    Foreach { collection, foreach },
+    IsEmpty { is_empty },
+    IsNonEmpty { is_nonempty },
+    Map { collection, map },
+    Prepend { prepend, collection },
+    Take { take, collection },
+}
+
 /// The `Append` function creates a new array that is the result of combining the
 /// base Array followed by the `elems`.
 ///
@@ -14,7 +26,7 @@ query![Append, Drop, Filter, Foreach, IsEmpty, IsNonEmpty, Map, Prepend, Take];
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/append).
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Append<'a> {
     append: Expr<'a>,
     collection: Expr<'a>,
@@ -46,7 +58,7 @@ impl<'a> Append<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/drop).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Drop<'a> {
     drop: Expr<'a>,
     collection: Expr<'a>,
@@ -73,7 +85,7 @@ impl<'a> Drop<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/filter).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Filter<'a> {
     filter: Expr<'a>,
     collection: Expr<'a>,
@@ -99,7 +111,7 @@ impl<'a> Filter<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/foreach).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Foreach<'a> {
     collection: Expr<'a>,
     foreach: Expr<'a>,
@@ -119,7 +131,7 @@ impl<'a> Foreach<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/isempty).
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct IsEmpty<'a> {
     is_empty: Expr<'a>,
 }
@@ -137,7 +149,7 @@ impl<'a> IsEmpty<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/isnonempty).
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct IsNonEmpty<'a> {
     is_nonempty: Expr<'a>,
 }
@@ -157,7 +169,7 @@ impl<'a> IsNonEmpty<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/map).
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Map<'a> {
     collection: Expr<'a>,
     map: Expr<'a>,
@@ -178,7 +190,7 @@ impl<'a> Map<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/prepend).
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Prepend<'a> {
     prepend: Expr<'a>,
     collection: Expr<'a>,
@@ -208,7 +220,7 @@ impl<'a> Prepend<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/collection/take).
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Take<'a> {
     take: Expr<'a>,
     collection: Expr<'a>,
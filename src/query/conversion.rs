@@ -3,6 +3,13 @@ use crate::{expr::Expr, query::Query};
 
 query![ToDate, ToNumber, ToString, ToTime];
 
+into_owned! {
+    ToDate { to_date },
+    ToNumber { to_number },
+    ToString { to_string },
+    ToTime { to_time },
+}
+
 /// The `ToDate` function converts a value to a date type, if possible.
 ///
 /// Attempting to convert a value to a date which has no date representation
@@ -10,7 +17,7 @@ query![ToDate, ToNumber, ToString, ToTime];
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/conversion/todate).
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ToDate<'a> {
     to_date: Expr<'a>,
 }
@@ -30,7 +37,7 @@ impl<'a> ToDate<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/conversion/tonumber).
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ToNumber<'a> {
     to_number: Expr<'a>,
 }
@@ -50,7 +57,7 @@ impl<'a> ToNumber<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/conversion/tostring).
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ToString<'a> {
     to_string: Expr<'a>,
 }
@@ -70,7 +77,7 @@ impl<'a> ToString<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/conversion/totime).
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ToTime<'a> {
     to_time: Expr<'a>,
 }
@@ -3,11 +3,17 @@ use crate::{expr::Expr, query::Query};
 
 query![Date, Epoch, Time];
 
+into_owned! {
+    Date { date },
+    Epoch { epoch, unit },
+    Time { time },
+}
+
 /// The `Date` function constructs a Date from an ISO 8601 formatted string.
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/time_date/date)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Date<'a> {
     date: Expr<'a>,
 }
@@ -20,7 +26,7 @@ impl<'a> Date<'a> {
     }
 }
 
-#[derive(Serialize, Clone, Debug, Copy)]
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq, Eq, Hash)]
 pub enum EpochUnit {
     #[serde(rename = "second")]
     Second,
@@ -40,7 +46,7 @@ pub enum EpochUnit {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/time_date/epoch)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Epoch<'a> {
     epoch: Expr<'a>,
     unit: EpochUnit,
@@ -65,7 +71,7 @@ impl<'a> Epoch<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/time_date/time)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Time<'a> {
     time: Expr<'a>,
 }
@@ -7,14 +7,27 @@ use chrono::{DateTime, Utc};
 
 query![And, Or, Not, Contains, Exists, Equals, Lt, Lte, Gt, Gte];
 
+into_owned! {
+    And { and },
+    Or { or },
+    Not { not },
+    Equals { equals },
+    Lt { lt },
+    Lte { lte },
+    Gt { gt },
+    Gte { gte },
+    Contains { contains, in_ },
+    Exists { exists, timestamp },
+}
+
 /// The `And` function computes the conjunction of a list of boolean values,
 /// returning `true` if all elements are "true", and `false` otherwise.
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/and)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct And<'a> {
-    and: Vec<Expr<'a>>,
+    pub(crate) and: Vec<Expr<'a>>,
 }
 
 impl<'a> And<'a> {
@@ -44,9 +57,9 @@ where
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/or)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Or<'a> {
-    or: Vec<Expr<'a>>,
+    pub(crate) or: Vec<Expr<'a>>,
 }
 
 impl<'a> Or<'a> {
@@ -76,7 +89,7 @@ where
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/not)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Not<'a> {
     not: Expr<'a>,
 }
@@ -91,7 +104,7 @@ impl<'a> Not<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/equals)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Equals<'a> {
     equals: Vec<Expr<'a>>,
 }
@@ -123,7 +136,7 @@ where
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/lt)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Lt<'a> {
     lt: Vec<Expr<'a>>,
 }
@@ -155,7 +168,7 @@ where
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/lte)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Lte<'a> {
     lte: Vec<Expr<'a>>,
 }
@@ -187,7 +200,7 @@ where
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/gt)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Gt<'a> {
     gt: Vec<Expr<'a>>,
 }
@@ -219,7 +232,7 @@ where
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/gte)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Gte<'a> {
     gte: Vec<Expr<'a>>,
 }
@@ -251,7 +264,7 @@ where
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/contains)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Contains<'a> {
     contains: Vec<Expr<'a>>,
     #[serde(rename = "in")]
@@ -278,7 +291,7 @@ impl<'a> Contains<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/logical/exists)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Exists<'a> {
     exists: Expr<'a>,
     #[serde(rename = "ts", skip_serializing_if = "Option::is_none")]
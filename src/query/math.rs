@@ -8,11 +8,48 @@ query![
     Sqrt, Subtract, Tan, Tanh, Trunc
 ];
 
+into_owned! {
+    Abs { abs },
+    Acos { acos },
+    Add { add },
+    Asin { asin },
+    Atan { atan },
+    BitAnd { bitand },
+    BitNot { bitnot },
+    BitOr { bitor },
+    BitXor { bitxor },
+    Ceil { ceil },
+    Cos { cos },
+    Cosh { cosh },
+    Degrees { degrees },
+    Divide { divide },
+    Exp { exp },
+    Floor { floor },
+    Hypot { hypot, b },
+    Ln { ln },
+    Log { log },
+    Max { max },
+    Min { min },
+    Modulo { modulo },
+    Multiply { multiply },
+    Pow { pow, exp },
+    Radians { radians },
+    Round { round, precision },
+    Sign { sign },
+    Sin { sin },
+    Sinh { sinh },
+    Sqrt { sqrt },
+    Subtract { subtract },
+    Tan { tan },
+    Tanh { tanh },
+    Trunc { trunc, precision },
+}
+
 /// The `Abs` function is used to get the absolute value of a number.
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/abs)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Abs<'a> {
     abs: Expr<'a>,
 }
@@ -29,7 +66,7 @@ impl<'a> Abs<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/acos)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Acos<'a> {
     acos: Expr<'a>,
 }
@@ -44,9 +81,9 @@ impl<'a> Acos<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/add)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Add<'a> {
-    add: Expr<'a>,
+    pub(crate) add: Expr<'a>,
 }
 
 impl<'a> Add<'a> {
@@ -61,7 +98,7 @@ impl<'a> Add<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/asin)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Asin<'a> {
     asin: Expr<'a>,
 }
@@ -78,7 +115,7 @@ impl<'a> Asin<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/atan)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Atan<'a> {
     atan: Expr<'a>,
 }
@@ -96,9 +133,9 @@ impl<'a> Atan<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/bitand)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct BitAnd<'a> {
-    bitand: Expr<'a>,
+    pub(crate) bitand: Expr<'a>,
 }
 
 impl<'a> BitAnd<'a> {
@@ -115,7 +152,7 @@ impl<'a> BitAnd<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/bitnot)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct BitNot<'a> {
     bitnot: Expr<'a>,
 }
@@ -135,9 +172,9 @@ impl<'a> BitNot<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/bitor)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct BitOr<'a> {
-    bitor: Expr<'a>,
+    pub(crate) bitor: Expr<'a>,
 }
 
 impl<'a> BitOr<'a> {
@@ -155,9 +192,9 @@ impl<'a> BitOr<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/bitxor)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct BitXor<'a> {
-    bitxor: Expr<'a>,
+    pub(crate) bitxor: Expr<'a>,
 }
 
 impl<'a> BitXor<'a> {
@@ -173,7 +210,7 @@ impl<'a> BitXor<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/ceil)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Ceil<'a> {
     ceil: Expr<'a>,
 }
@@ -190,7 +227,7 @@ impl<'a> Ceil<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/cos)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Cos<'a> {
     cos: Expr<'a>,
 }
@@ -207,7 +244,7 @@ impl<'a> Cos<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/cosh)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Cosh<'a> {
     cosh: Expr<'a>,
 }
@@ -224,7 +261,7 @@ impl<'a> Cosh<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/degrees)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Degrees<'a> {
     degrees: Expr<'a>,
 }
@@ -246,9 +283,9 @@ impl<'a> Degrees<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/divide)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Divide<'a> {
-    divide: Expr<'a>,
+    pub(crate) divide: Expr<'a>,
 }
 
 impl<'a> Divide<'a> {
@@ -264,7 +301,7 @@ impl<'a> Divide<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/exp)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Exp<'a> {
     exp: Expr<'a>,
 }
@@ -280,7 +317,7 @@ impl<'a> Exp<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/floor)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Floor<'a> {
     floor: Expr<'a>,
 }
@@ -298,7 +335,7 @@ impl<'a> Floor<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/hypot)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Hypot<'a> {
     hypot: Expr<'a>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -326,7 +363,7 @@ impl<'a> Hypot<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/ln)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Ln<'a> {
     ln: Expr<'a>,
 }
@@ -341,7 +378,7 @@ impl<'a> Ln<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/log)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Log<'a> {
     log: Expr<'a>,
 }
@@ -356,7 +393,7 @@ impl<'a> Log<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/max)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Max<'a> {
     max: Expr<'a>,
 }
@@ -371,7 +408,7 @@ impl<'a> Max<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/min)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Min<'a> {
     min: Expr<'a>,
 }
@@ -394,9 +431,9 @@ impl<'a> Min<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/modulo)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Modulo<'a> {
-    modulo: Expr<'a>,
+    pub(crate) modulo: Expr<'a>,
 }
 
 impl<'a> Modulo<'a> {
@@ -416,9 +453,9 @@ impl<'a> Modulo<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/multiply)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Multiply<'a> {
-    multiply: Expr<'a>,
+    pub(crate) multiply: Expr<'a>,
 }
 
 impl<'a> Multiply<'a> {
@@ -434,7 +471,7 @@ impl<'a> Multiply<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/pow)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Pow<'a> {
     pow: Expr<'a>,
     exp: Expr<'a>,
@@ -457,7 +494,7 @@ impl<'a> Pow<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/radians)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Radians<'a> {
     radians: Expr<'a>,
 }
@@ -475,7 +512,7 @@ impl<'a> Radians<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/round)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Round<'a> {
     round: Expr<'a>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -506,7 +543,7 @@ impl<'a> Round<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/sign)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Sign<'a> {
     sign: Expr<'a>,
 }
@@ -524,7 +561,7 @@ impl<'a> Sign<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/sin)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Sin<'a> {
     sin: Expr<'a>,
 }
@@ -539,7 +576,7 @@ impl<'a> Sin<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/sinh)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Sinh<'a> {
     sinh: Expr<'a>,
 }
@@ -554,7 +591,7 @@ impl<'a> Sinh<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/sqrt)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Sqrt<'a> {
     sqrt: Expr<'a>,
 }
@@ -569,9 +606,9 @@ impl<'a> Sqrt<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/subtract)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Subtract<'a> {
-    subtract: Expr<'a>,
+    pub(crate) subtract: Expr<'a>,
 }
 
 impl<'a> Subtract<'a> {
@@ -588,7 +625,7 @@ impl<'a> Subtract<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/tan)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Tan<'a> {
     tan: Expr<'a>,
 }
@@ -603,7 +640,7 @@ impl<'a> Tan<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/tanh)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Tanh<'a> {
     tanh: Expr<'a>,
 }
@@ -620,7 +657,7 @@ impl<'a> Tanh<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/math/round)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Trunc<'a> {
     trunc: Expr<'a>,
     #[serde(skip_serializing_if = "Option::is_none")]
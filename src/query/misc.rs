@@ -3,6 +3,19 @@ use crate::{expr::Expr, query::Query};
 
 query![Abort, Class, Classes, Database, Databases, Function, Functions, Index, Indexes, NewId];
 
+into_owned! {
+    Abort { abort },
+    Class { class, scope },
+    Classes { classes },
+    Function { function, scope },
+    Functions { functions },
+    Database { database, scope },
+    Databases { databases },
+    Index { index, scope },
+    Indexes { indexes },
+    NewId { new_id },
+}
+
 /// This `Abort` function terminates the current transaction and augments the
 /// returned error with the associated message.
 ///
@@ -12,7 +25,7 @@ query![Abort, Class, Classes, Database, Databases, Function, Functions, Index, I
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/abort)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Abort<'a> {
     abort: Expr<'a>,
 }
@@ -27,14 +40,25 @@ impl<'a> Abort<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/class)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Class<'a> {
     class: Expr<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<Expr<'a>>,
 }
 
 impl<'a> Class<'a> {
     pub fn find(name: impl Into<Expr<'a>>) -> Self {
-        Self { class: name.into() }
+        Self {
+            class: name.into(),
+            scope: None,
+        }
+    }
+
+    /// Looks the class up from a child database instead of the current one.
+    pub fn scope(&mut self, database: impl Into<Expr<'a>>) -> &mut Self {
+        self.scope = Some(database.into());
+        self
     }
 }
 
@@ -46,7 +70,7 @@ impl<'a> Class<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/classes)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Classes<'a> {
     classes: Option<Expr<'a>>,
 }
@@ -67,17 +91,26 @@ impl<'a> Classes<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/function)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Function<'a> {
     function: Expr<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<Expr<'a>>,
 }
 
 impl<'a> Function<'a> {
     pub fn find(name: impl Into<Expr<'a>>) -> Self {
         Self {
             function: name.into(),
+            scope: None,
         }
     }
+
+    /// Looks the function up from a child database instead of the current one.
+    pub fn scope(&mut self, database: impl Into<Expr<'a>>) -> &mut Self {
+        self.scope = Some(database.into());
+        self
+    }
 }
 
 /// The `Functions` function when executed with `Paginate` returns an array of Refs
@@ -88,7 +121,7 @@ impl<'a> Function<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/functions)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Functions<'a> {
     functions: Option<Expr<'a>>,
 }
@@ -109,17 +142,26 @@ impl<'a> Functions<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/database)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Database<'a> {
     database: Expr<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<Expr<'a>>,
 }
 
 impl<'a> Database<'a> {
     pub fn find(name: impl Into<Expr<'a>>) -> Self {
         Self {
             database: name.into(),
+            scope: None,
         }
     }
+
+    /// Looks the database up from a child database instead of the current one.
+    pub fn scope(&mut self, database: impl Into<Expr<'a>>) -> &mut Self {
+        self.scope = Some(database.into());
+        self
+    }
 }
 
 /// The `Databases` function when executed with `Paginate` returns an array of Refs
@@ -130,7 +172,7 @@ impl<'a> Database<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/databases)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Databases<'a> {
     databases: Option<Expr<'a>>,
 }
@@ -151,14 +193,25 @@ impl<'a> Databases<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/index)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Index<'a> {
     index: Expr<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<Expr<'a>>,
 }
 
 impl<'a> Index<'a> {
     pub fn find(name: impl Into<Expr<'a>>) -> Self {
-        Self { index: name.into() }
+        Self {
+            index: name.into(),
+            scope: None,
+        }
+    }
+
+    /// Looks the index up from a child database instead of the current one.
+    pub fn scope(&mut self, database: impl Into<Expr<'a>>) -> &mut Self {
+        self.scope = Some(database.into());
+        self
     }
 }
 
@@ -170,7 +223,7 @@ impl<'a> Index<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/databases)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Indexes<'a> {
     indexes: Option<Expr<'a>>,
 }
@@ -195,7 +248,7 @@ impl<'a> Indexes<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/misc/newid)
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct NewId<'a> {
     new_id: Expr<'a>,
 }
@@ -239,6 +292,22 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_class_scope() {
+        let mut fun = Class::find("housecats");
+        fun.scope(Database::find("cats"));
+
+        let query = Query::from(fun);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        let expected = json!({
+            "class": "housecats",
+            "scope": { "database": "cats" },
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
     #[test]
     fn test_classes_all() {
         let fun = Classes::all();
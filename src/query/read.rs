@@ -7,6 +7,14 @@ use chrono::{DateTime, Utc};
 
 query![Get, KeyFromSecret, Paginate, Select, SelectAll];
 
+into_owned! {
+    Get { get, timestamp },
+    KeyFromSecret { key_from_secret },
+    Paginate { paginate, size, events, sources, timestamp, after, before },
+    Select { select, from, default },
+    SelectAll { select_all, from },
+}
+
 /// The `Get` function retrieves a single instance identified by `ref`.
 ///
 /// An optional `timestamp` can be provided to retrieve the instance which
@@ -15,7 +23,7 @@ query![Get, KeyFromSecret, Paginate, Select, SelectAll];
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/read/get)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Get<'a> {
     get: Expr<'a>,
     #[serde(rename = "ts", skip_serializing_if = "Option::is_none")]
@@ -40,7 +48,7 @@ impl<'a> Get<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/read/keyfromsecret)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct KeyFromSecret<'a> {
     key_from_secret: Expr<'a>,
 }
@@ -62,11 +70,14 @@ impl<'a> KeyFromSecret<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/read/paginate)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Paginate<'a> {
     paginate: Expr<'a>,
+    #[serde(default = "default_size")]
     size: u32,
+    #[serde(default)]
     events: bool,
+    #[serde(default)]
     sources: bool,
     #[serde(rename = "ts", skip_serializing_if = "Option::is_none")]
     timestamp: Option<Expr<'a>>,
@@ -76,12 +87,16 @@ pub struct Paginate<'a> {
     before: Option<Expr<'a>>,
 }
 
+fn default_size() -> u32 {
+    64
+}
+
 impl<'a> Paginate<'a> {
     /// Input `Set` or a `Ref`
     pub fn new(input: impl Into<Expr<'a>>) -> Self {
         Self {
             paginate: input.into(),
-            size: 64,
+            size: default_size(),
             events: false,
             sources: false,
             timestamp: None,
@@ -155,7 +170,7 @@ impl<'a> Paginate<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/read/select)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Select<'a> {
     select: Array<'a>,
     from: Expr<'a>,
@@ -188,7 +203,7 @@ impl<'a> Select<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/read/selectall)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SelectAll<'a> {
     select_all: Array<'a>,
     from: Expr<'a>,
@@ -6,12 +6,21 @@ use crate::{
 
 query![Difference, Distinct, Intersection, Join, Match, Union];
 
+into_owned! {
+    Difference { difference },
+    Distinct { distinct },
+    Intersection { intersection },
+    Join { join, with },
+    Match { match_, terms },
+    Union { union },
+}
+
 /// The `Difference` function returns a `SetRef` object that represents all elements
 /// in the first `SetRef` which are not in the difference `SetRef`(s).
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/set/difference)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Difference<'a> {
     difference: Array<'a>,
 }
@@ -48,7 +57,7 @@ where
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/set/distinct)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Distinct<'a> {
     distinct: Expr<'a>,
 }
@@ -64,7 +73,7 @@ impl<'a> Distinct<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/set/intersection)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Intersection<'a> {
     intersection: Array<'a>,
 }
@@ -101,7 +110,7 @@ where
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/set/join)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Join<'a> {
     join: Expr<'a>,
     with: Expr<'a>,
@@ -131,7 +140,7 @@ impl<'a> Join<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/set/match)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Match<'a> {
     #[serde(rename = "match")]
     match_: Expr<'a>,
@@ -157,7 +166,7 @@ impl<'a> Match<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/set/union)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Union<'a> {
     union: Array<'a>,
 }
@@ -20,6 +20,25 @@ query![
     UpperCase
 ];
 
+into_owned! {
+    CaseFold { casefold, normalizer },
+    Concat { concat, separator },
+    FindStr { findstr, find, start },
+    FindStrRegex { findstrregex, pattern, start, num_results },
+    LTrim { ltrim },
+    RTrim { rtrim },
+    Length { length },
+    LowerCase { lowercase },
+    Repeat { repeat, number },
+    ReplaceStr { replacestr, find, replace },
+    ReplaceStrRegex { replacestrregex, pattern, replace, first },
+    Space { space },
+    SubString { substring, start, length },
+    TitleCase { titlecase },
+    Trim { trim },
+    UpperCase { uppercase },
+}
+
 /// The CaseFold function returns a normalized string.
 ///
 /// When strings are transformed into their normalized forms,
@@ -29,14 +48,14 @@ query![
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/casefold)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct CaseFold<'a> {
     casefold: Expr<'a>,
     #[serde(skip_serializing_if = "Option::is_none")]
     normalizer: Option<Normalizer>,
 }
 
-#[derive(Serialize, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Normalizer {
     NFKCCaseFold,
     NFC,
@@ -64,7 +83,7 @@ impl<'a> CaseFold<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/concat)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Concat<'a> {
     concat: Expr<'a>,
     separator: Expr<'a>,
@@ -84,7 +103,7 @@ impl<'a> Concat<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/findstr)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FindStr<'a> {
     findstr: Expr<'a>,
     find: Expr<'a>,
@@ -111,7 +130,7 @@ impl<'a> FindStr<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/findstrregex)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FindStrRegex<'a> {
     findstrregex: Expr<'a>,
     pattern: Expr<'a>,
@@ -145,7 +164,7 @@ impl<'a> FindStrRegex<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/ltrim)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct LTrim<'a> {
     ltrim: Expr<'a>,
 }
@@ -163,7 +182,7 @@ impl<'a> LTrim<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/rtrim)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct RTrim<'a> {
     rtrim: Expr<'a>,
 }
@@ -180,7 +199,7 @@ impl<'a> RTrim<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/length)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Length<'a> {
     length: Expr<'a>,
 }
@@ -198,7 +217,7 @@ impl<'a> Length<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/lowercase)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct LowerCase<'a> {
     lowercase: Expr<'a>,
 }
@@ -216,7 +235,7 @@ impl<'a> LowerCase<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/repeat)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Repeat<'a> {
     repeat: Expr<'a>,
     number: Expr<'a>,
@@ -237,7 +256,7 @@ impl<'a> Repeat<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/replacestr)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ReplaceStr<'a> {
     replacestr: Expr<'a>,
     find: Expr<'a>,
@@ -265,11 +284,12 @@ impl<'a> ReplaceStr<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/replacestrregex)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ReplaceStrRegex<'a> {
     replacestrregex: Expr<'a>,
     pattern: Expr<'a>,
     replace: Expr<'a>,
+    #[serde(default)]
     first: bool,
 }
 
@@ -293,7 +313,7 @@ impl<'a> ReplaceStrRegex<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/space)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Space<'a> {
     space: Expr<'a>,
 }
@@ -311,7 +331,7 @@ impl<'a> Space<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/substring)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SubString<'a> {
     substring: Expr<'a>,
     start: Expr<'a>,
@@ -339,7 +359,7 @@ impl<'a> SubString<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/titlecase)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct TitleCase<'a> {
     titlecase: Expr<'a>,
 }
@@ -357,7 +377,7 @@ impl<'a> TitleCase<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/trim)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Trim<'a> {
     trim: Expr<'a>,
 }
@@ -375,7 +395,7 @@ impl<'a> Trim<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/string/uppercase)
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct UpperCase<'a> {
     uppercase: Expr<'a>,
 }
@@ -22,7 +22,13 @@ pub use update::*;
 
 query![Delete, Remove, Replace];
 
-#[derive(Serialize, Debug, Clone, Copy)]
+into_owned! {
+    Delete { delete },
+    Remove { remove, timestamp, action },
+    Replace { replace, params },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Action {
     #[serde(rename = "create")]
     Create,
@@ -37,7 +43,7 @@ pub enum Action {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/delete)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Delete<'a> {
     delete: Expr<'a>,
 }
@@ -58,7 +64,7 @@ impl<'a> Delete<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/remove)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Remove<'a> {
     remove: Expr<'a>,
     #[serde(rename = "ts")]
@@ -82,7 +88,7 @@ impl<'a> Remove<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/replace)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Replace<'a> {
     replace: Expr<'a>,
     params: Expr<'a>,
@@ -2,6 +2,12 @@ use crate::{expr::Expr, query::Query};
 
 query!(Create);
 
+into_owned! {
+    Create { create, params },
+    InstanceData { data, credentials },
+    InstanceParams { object },
+}
+
 /// The `Create` function adds a new instance to a class.
 ///
 /// The `class_ref` parameter indicates what class of instance should be
@@ -9,19 +15,21 @@ query!(Create);
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/create)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Create<'a> {
     create: Expr<'a>,
     params: InstanceParams<'a>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct InstanceData<'a> {
     data: Expr<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credentials: Option<Expr<'a>>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 struct InstanceParams<'a> {
     object: InstanceData<'a>,
 }
@@ -33,6 +41,12 @@ impl<'a> Create<'a> {
             params: InstanceParams::new(data),
         }
     }
+
+    /// Credentials for authenticating as the created instance.
+    pub fn credentials(&mut self, credentials: impl Into<Expr<'a>>) -> &mut Self {
+        self.params.object.credentials = Some(credentials.into());
+        self
+    }
 }
 
 impl<'a> InstanceParams<'a> {
@@ -41,7 +55,10 @@ impl<'a> InstanceParams<'a> {
         E: Into<Expr<'a>>,
     {
         Self {
-            object: InstanceData { data: data.into() },
+            object: InstanceData {
+                data: data.into(),
+                credentials: None,
+            },
         }
     }
 }
@@ -86,6 +103,26 @@ mod tests {
         assert_eq!(expected, serialized);
     }
 
+    #[test]
+    fn test_create_credentials() {
+        let mut obj = Object::default();
+        obj.insert("name", "Musti");
+
+        let mut credentials = Object::default();
+        credentials.insert("password", "meowmeow");
+
+        let mut create = Create::new(Ref::class("test"), obj);
+        create.credentials(credentials);
+
+        let query = Query::from(create);
+        let serialized = serde_json::to_value(&query).unwrap();
+
+        assert_eq!(
+            json!({ "object": { "password": "meowmeow" } }),
+            serialized["params"]["object"]["credentials"]
+        );
+    }
+
     #[test]
     fn test_create_eval() {
         let mut obj = Object::default();
@@ -6,6 +6,12 @@ use std::borrow::Cow;
 
 boxed_query!(CreateClass);
 
+into_owned! {
+    CreateClass { create_class },
+    ClassParamsInternal { name, data, history_days, ttl_days, permissions },
+    ClassParams { object },
+}
+
 /// The `CreateClass` function is used to create a class which groups instance
 /// objects.
 ///
@@ -15,7 +21,7 @@ boxed_query!(CreateClass);
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/createclass).
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct CreateClass<'a> {
     create_class: ClassParams<'a>,
 }
@@ -28,7 +34,7 @@ impl<'a> CreateClass<'a> {
     }
 }
 
-#[derive(Debug, Default, Serialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 struct ClassParamsInternal<'a> {
     name: Cow<'a, str>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -41,7 +47,7 @@ struct ClassParamsInternal<'a> {
     permissions: Option<ClassPermission<'a>>,
 }
 
-#[derive(Debug, Default, Serialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct ClassParams<'a> {
     object: ClassParamsInternal<'a>,
 }
@@ -7,6 +7,12 @@ use std::borrow::Cow;
 
 query!(CreateDatabase);
 
+into_owned! {
+    CreateDatabase { create_database },
+    DatabaseParamsInternal { name, api_version, data, priority },
+    DatabaseParams { object },
+}
+
 /// The `CreateDatabase` function adds a new database to the cluster with the
 /// specified parameters.
 ///
@@ -14,12 +20,12 @@ query!(CreateDatabase);
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/createdatabase)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct CreateDatabase<'a> {
     create_database: DatabaseParams<'a>,
 }
 
-#[derive(Debug, Default, Serialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct DatabaseParamsInternal<'a> {
     name: Cow<'a, str>,
@@ -30,7 +36,7 @@ pub struct DatabaseParamsInternal<'a> {
     priority: Option<u16>,
 }
 
-#[derive(Debug, Default, Serialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct DatabaseParams<'a> {
     object: DatabaseParamsInternal<'a>,
 }
@@ -6,12 +6,18 @@ use std::borrow::Cow;
 
 boxed_query!(CreateFunction);
 
+into_owned! {
+    CreateFunction { create_function },
+    FunctionParamsInternal { name, body, data },
+    FunctionParams { object },
+}
+
 /// The `CreateFunction` operation adds a new user-defined function with the
 /// specified parameters.
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/createfunction).
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct CreateFunction<'a> {
     create_function: FunctionParams<'a>,
 }
@@ -24,7 +30,7 @@ impl<'a> CreateFunction<'a> {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 struct FunctionParamsInternal<'a> {
     name: Cow<'a, str>,
     body: Expr<'a>,
@@ -32,7 +38,7 @@ struct FunctionParamsInternal<'a> {
     data: Option<Expr<'a>>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct FunctionParams<'a> {
     object: FunctionParamsInternal<'a>,
 }
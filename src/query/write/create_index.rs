@@ -6,6 +6,24 @@ use std::borrow::Cow;
 
 boxed_query!(CreateIndex);
 
+into_owned! {
+    CreateIndex { create_index },
+    IndexField { 0 },
+    IndexBinding { 0 },
+    Term { object },
+    ValueObject { field, binding, reverse },
+    IndexValue { object },
+    IndexParamsInternal { name, source, active, unique, serialized, terms, values, partitions, permissions, data },
+    IndexParams { object },
+}
+
+into_owned!(
+    enum TermObject {
+        Field,
+        Binding,
+    }
+);
+
 /// The `CreateIndex` function adds a new index to the cluster with the specified
 /// parameters.
 ///
@@ -19,7 +37,7 @@ boxed_query!(CreateIndex);
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/createindex)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct CreateIndex<'a> {
     create_index: IndexParams<'a>,
 }
@@ -32,15 +50,15 @@ impl<'a> CreateIndex<'a> {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct IndexField<'a>(Vec<Cow<'a, str>>);
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct IndexBinding<'a>(Cow<'a, str>);
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub enum TermObject<'a> {
     #[serde(rename = "field")]
@@ -56,18 +74,19 @@ pub enum TermObject<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/indexconfig#term-objects)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct Term<'a> {
     object: TermObject<'a>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct ValueObject<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     field: Option<IndexField<'a>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     binding: Option<IndexBinding<'a>>,
+    #[serde(default)]
     reverse: bool,
 }
 
@@ -79,18 +98,21 @@ pub struct ValueObject<'a> {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/indexconfig#value-objects)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct IndexValue<'a> {
     object: ValueObject<'a>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct IndexParamsInternal<'a> {
     name: Cow<'a, str>,
     source: Expr<'a>,
+    #[serde(default)]
     active: bool,
+    #[serde(default)]
     unique: bool,
+    #[serde(default)]
     serialized: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     terms: Option<Vec<Term<'a>>>,
@@ -104,7 +126,7 @@ pub struct IndexParamsInternal<'a> {
     data: Option<Expr<'a>>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct IndexParams<'a> {
     object: IndexParamsInternal<'a>,
 }
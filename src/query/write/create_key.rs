@@ -5,7 +5,13 @@ use crate::{
 
 boxed_query!(CreateKey);
 
-#[derive(Debug, Serialize, Clone, Copy)]
+into_owned! {
+    CreateKey { create_key },
+    KeyParamsInternal { database, role, priority, data },
+    KeyParams { object },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Role {
     #[serde(rename = "admin")]
     Admin,
@@ -22,7 +28,7 @@ pub enum Role {
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/createkey)
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct CreateKey<'a> {
     create_key: KeyParams<'a>,
 }
@@ -33,7 +39,7 @@ impl<'a> CreateKey<'a> {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct KeyParamsInternal<'a> {
     database: Expr<'a>,
@@ -43,7 +49,7 @@ pub struct KeyParamsInternal<'a> {
     data: Option<Expr<'a>>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct KeyParams<'a> {
     object: KeyParamsInternal<'a>,
 }
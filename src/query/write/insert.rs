@@ -6,12 +6,18 @@ use chrono::{DateTime, Utc};
 
 boxed_query!(Insert);
 
+into_owned! {
+    Insert { insert, timestamp, action, params },
+    InsertParams { object },
+    InsertObject { data, credentials, delegates },
+}
+
 /// The Insert function adds an event to an instance’s history at a specified
 /// timestamp.
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/insert)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Insert<'a> {
     insert: Expr<'a>,
     #[serde(rename = "ts")]
@@ -20,12 +26,12 @@ pub struct Insert<'a> {
     params: InsertParams<'a>,
 }
 
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct InsertParams<'a> {
     object: InsertObject<'a>,
 }
 
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct InsertObject<'a> {
     data: Expr<'a>,
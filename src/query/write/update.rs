@@ -2,6 +2,12 @@ use crate::{expr::Expr, query::Query};
 
 query!(Update);
 
+into_owned! {
+    Update { update, params },
+    UpdateParams { object },
+    UpdateObject { data, credentials, delegates },
+}
+
 /// The `Update` operation only modifies the specified fields in the instances
 /// pointed to by `ref`.
 ///
@@ -13,18 +19,18 @@ query!(Update);
 ///
 /// Read the
 /// [docs](https://docs.fauna.com/fauna/current/reference/queryapi/write/update)
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Update<'a> {
     update: Expr<'a>,
     params: UpdateParams<'a>,
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct UpdateParams<'a> {
     object: UpdateObject<'a>,
 }
 
-#[derive(Serialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
 #[doc(hidden)]
 pub struct UpdateObject<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1,2 +1,131 @@
 //! Serde (de-)serializer functions for Fauna types.
+//!
+//! The `timestamp`, `date`, `reference` and `bytes` modules are meant to be used with
+//! `#[serde(with = "...")]`, annotating the field as the corresponding Fauna
+//! type when serialized with [to_expr](../fn.to_expr.html).
+#[doc(hidden)]
 pub mod base64_bytes;
+pub mod de;
+pub mod ser;
+
+/// Annotates a `DateTime<Utc>` field as a Fauna timestamp.
+pub mod timestamp {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(ts: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(super::ser::TIMESTAMP, ts)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DateTime::deserialize(deserializer)
+    }
+}
+
+/// Annotates a `NaiveDate` field as a Fauna date.
+pub mod date {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(super::ser::DATE, date)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        NaiveDate::deserialize(deserializer)
+    }
+}
+
+/// Annotates a `Ref` field as a Fauna ref.
+pub mod reference {
+    use crate::expr::Ref;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(reference: &Ref, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(super::ser::REF, reference)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Ref<'static>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ref::deserialize(deserializer)
+    }
+}
+
+/// Writes a `Vec<u8>` or `&[u8]` field as Fauna `Bytes` instead of an array of
+/// numbers.
+pub mod bytes {
+    use serde::{
+        de::{self, SeqAccess, Visitor},
+        Deserializer, Serializer,
+    };
+    use std::fmt;
+
+    pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<[u8]> + ?Sized,
+        S: Serializer,
+    {
+        serializer.serialize_bytes(bytes.as_ref())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("bytes")
+        }
+
+        fn visit_bytes<E>(self, value: &[u8]) -> Result<Vec<u8>, E>
+        where
+            E: de::Error,
+        {
+            Ok(value.to_vec())
+        }
+
+        fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Vec<u8>, E>
+        where
+            E: de::Error,
+        {
+            Ok(value)
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Vec<u8>, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+
+            Ok(bytes)
+        }
+    }
+}
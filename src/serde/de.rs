@@ -0,0 +1,527 @@
+//! A serde `Deserializer` for reading Fauna values into Rust types.
+use crate::{
+    client::{AnnotatedValue, SimpleValue, Value},
+    error::{DecodeError, Error},
+    expr::Number,
+    serde::ser::REF,
+};
+use chrono::SecondsFormat;
+use serde::de::{
+    self, value::StringDeserializer, DeserializeOwned, DeserializeSeed, EnumAccess,
+    IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::{collections::btree_map, vec};
+
+/// Deserializes a Fauna `Value` into any type implementing `Deserialize`.
+///
+/// Timestamps can be read into a `DateTime<Utc>`, dates into a `NaiveDate`,
+/// bytes into a `Vec<u8>` and refs into a `Ref`. Reading into a `Value` keeps
+/// the annotations.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// # use std::collections::BTreeMap;
+/// #
+/// #[derive(serde_derive::Deserialize)]
+/// struct Cat {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let mut data = BTreeMap::new();
+/// data.insert("name", Value::from("Musti"));
+/// data.insert("age", Value::from(7));
+///
+/// let cat: Cat = faunadb::from_value(Value::from(data)).unwrap();
+/// assert_eq!("Musti", cat.name);
+/// ```
+pub fn from_value<T>(value: Value) -> crate::Result<T>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(value).map_err(Error::DecodeError)
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = DecodeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Simple(SimpleValue::String(s)) => visitor.visit_string(s),
+            Value::Simple(SimpleValue::Number(Number::UInt(u))) => visitor.visit_u64(u),
+            Value::Simple(SimpleValue::Number(Number::Int(i))) => visitor.visit_i64(i),
+            Value::Simple(SimpleValue::Number(Number::Double(d))) => visitor.visit_f64(d),
+            Value::Simple(SimpleValue::Number(Number::Float(f))) => visitor.visit_f32(f),
+            Value::Simple(SimpleValue::Boolean(b)) => visitor.visit_bool(b),
+            Value::Simple(SimpleValue::Array(values)) => {
+                visitor.visit_seq(SeqDeserializer::new(values))
+            }
+            Value::Simple(SimpleValue::Object(obj)) => visitor.visit_map(MapDeserializer::new(obj)),
+            Value::Simple(SimpleValue::Null) => visitor.visit_unit(),
+            Value::Annotated(annotated) => annotated_json(&annotated)?
+                .deserialize_any(visitor)
+                .map_err(de::Error::custom),
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Annotated(AnnotatedValue::Timestamp(ts)) => {
+                visitor.visit_string(ts.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            }
+            Value::Annotated(AnnotatedValue::Date(date)) => visitor.visit_string(date.to_string()),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Annotated(AnnotatedValue::Bytes(bytes)) => {
+                visitor.visit_byte_buf(bytes.0.into_owned())
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Annotated(AnnotatedValue::Bytes(bytes)) => {
+                let values = bytes.0.iter().map(|b| Value::from(*b)).collect();
+                visitor.visit_seq(SeqDeserializer::new(values))
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Simple(SimpleValue::Null) => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Annotated(AnnotatedValue::Ref(reference)) if name == REF => {
+                serde_json::to_value(&reference)
+                    .map_err(de::Error::custom)?
+                    .deserialize_struct(name, fields, visitor)
+                    .map_err(de::Error::custom)
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Annotated(AnnotatedValue::Ref(reference)) => serde_json::to_value(&reference)
+                .map_err(de::Error::custom)?
+                .deserialize_map(visitor)
+                .map_err(de::Error::custom),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Simple(SimpleValue::String(variant)) => {
+                visitor.visit_enum(variant.into_deserializer())
+            }
+            Value::Simple(SimpleValue::Object(obj)) => {
+                let mut iter = obj.into_iter();
+
+                match (iter.next(), iter.next()) {
+                    (Some((variant, value)), None) => {
+                        visitor.visit_enum(EnumDeserializer { variant, value })
+                    }
+                    _ => Err(de::Error::invalid_value(
+                        de::Unexpected::Map,
+                        &"an object with a single key",
+                    )),
+                }
+            }
+            Value::Annotated(annotated) => annotated_json(&annotated)?
+                .deserialize_enum(name, variants, visitor)
+                .map_err(de::Error::custom),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char unit unit_struct
+        tuple tuple_struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, DecodeError> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// The annotated value in the format it was sent from Fauna, for visitors
+/// needing the annotation.
+fn annotated_json(annotated: &AnnotatedValue) -> Result<serde_json::Value, DecodeError> {
+    serde_json::to_value(annotated).map_err(de::Error::custom)
+}
+
+struct SeqDeserializer {
+    iter: vec::IntoIter<Value>,
+    index: usize,
+}
+
+impl SeqDeserializer {
+    fn new(values: Vec<Value>) -> Self {
+        Self {
+            iter: values.into_iter(),
+            index: 0,
+        }
+    }
+}
+
+impl<'de> SeqAccess<'de> for SeqDeserializer {
+    type Error = DecodeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some(value) => {
+                let index = self.index;
+                self.index += 1;
+
+                seed.deserialize(value)
+                    .map(Some)
+                    .map_err(|e| e.prepend(index.to_string()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapDeserializer {
+    iter: btree_map::IntoIter<String, Value>,
+    value: Option<(String, Value)>,
+}
+
+impl MapDeserializer {
+    fn new(obj: std::collections::BTreeMap<String, Value>) -> Self {
+        Self {
+            iter: obj.into_iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for MapDeserializer {
+    type Error = DecodeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            Some((key, value)) => {
+                let deserializer: StringDeserializer<DecodeError> = key.clone().into_deserializer();
+                self.value = Some((key, value));
+
+                seed.deserialize(deserializer).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some((key, value)) => seed.deserialize(value).map_err(|e| e.prepend(key)),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = DecodeError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let deserializer: StringDeserializer<DecodeError> =
+            self.variant.clone().into_deserializer();
+
+        let variant = seed.deserialize(deserializer)?;
+
+        Ok((
+            variant,
+            VariantDeserializer {
+                variant: self.variant,
+                value: self.value,
+            },
+        ))
+    }
+}
+
+struct VariantDeserializer {
+    variant: String,
+    value: Value,
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+    type Error = DecodeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.value {
+            Value::Simple(SimpleValue::Null) => Ok(()),
+            _ => Err(de::Error::invalid_type(
+                de::Unexpected::Map,
+                &"a unit variant",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let variant = self.variant;
+        seed.deserialize(self.value).map_err(|e| e.prepend(variant))
+    }
+
+    fn tuple_variant<V>(self, _: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let variant = self.variant;
+
+        de::Deserializer::deserialize_seq(self.value, visitor).map_err(|e| e.prepend(variant))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let variant = self.variant;
+
+        de::Deserializer::deserialize_map(self.value, visitor).map_err(|e| e.prepend(variant))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Error, prelude::*};
+    use chrono::{offset::TimeZone, DateTime, NaiveDate, Utc};
+    use serde_json::{self, json};
+    use std::collections::BTreeMap;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Owner {
+        name: String,
+        nicknames: Vec<String>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum Mood {
+        Happy,
+        Hungry { hours: u32 },
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Cat {
+        name: String,
+        age: u8,
+        weight: f64,
+        cute: bool,
+        owner: Owner,
+        friend: Option<String>,
+        born: NaiveDate,
+        created_at: DateTime<Utc>,
+        chip: Vec<u8>,
+        reference: Ref<'static>,
+        raw: Value,
+        moods: Vec<Mood>,
+    }
+
+    fn value(json: serde_json::Value) -> Value {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_from_value() {
+        let data = value(json!({
+            "name": "Musti",
+            "age": 7,
+            "weight": 4.5,
+            "cute": true,
+            "owner": { "name": "Julius", "nicknames": ["Pimeys"] },
+            "friend": null,
+            "born": { "@date": "2011-07-07" },
+            "created_at": { "@ts": "2019-05-26T16:20:00.000001Z" },
+            "chip": { "@bytes": "AQID" },
+            "reference": { "@ref": { "id": "musti" } },
+            "raw": { "@ts": "1970-01-01T00:01:00Z" },
+            "moods": ["Happy", { "Hungry": { "hours": 3 } }],
+        }));
+
+        let cat: Cat = crate::from_value(data).unwrap();
+
+        let expected = Cat {
+            name: String::from("Musti"),
+            age: 7,
+            weight: 4.5,
+            cute: true,
+            owner: Owner {
+                name: String::from("Julius"),
+                nicknames: vec![String::from("Pimeys")],
+            },
+            friend: None,
+            born: NaiveDate::from_ymd_opt(2011, 7, 7).unwrap(),
+            created_at: Utc.timestamp_opt(1_558_887_600, 1000).unwrap(),
+            chip: vec![1, 2, 3],
+            reference: Ref::instance("musti"),
+            raw: Value::from(Utc.timestamp_opt(60, 0).unwrap()),
+            moods: vec![Mood::Happy, Mood::Hungry { hours: 3 }],
+        };
+
+        assert_eq!(expected, cat);
+    }
+
+    #[test]
+    fn test_struct_named_ref() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Ref {
+            #[serde(rename = "@ref")]
+            inner: BTreeMap<String, String>,
+        }
+
+        let data = value(json!({ "@ref": { "id": "musti" } }));
+        let read: Ref = crate::from_value(data).unwrap();
+
+        assert_eq!(Some("musti"), read.inner.get("id").map(String::as_str));
+    }
+
+    #[test]
+    fn test_value_to_value() {
+        let data = value(json!({
+            "ref": { "@ref": { "id": "musti" } },
+            "data": { "names": ["Musti", { "@date": "2011-07-07" }] }
+        }));
+
+        let read: Value = crate::from_value(data.clone()).unwrap();
+
+        assert_eq!(data, read);
+    }
+
+    #[test]
+    fn test_error_path() {
+        let data = value(json!({
+            "owners": [
+                { "name": "Julius", "nicknames": [] },
+                { "name": "Pimeys", "nicknames": [1] },
+            ]
+        }));
+
+        match crate::from_value::<BTreeMap<String, Vec<Owner>>>(data) {
+            Err(Error::DecodeError(e)) => {
+                assert_eq!("/owners/1/nicknames/0", e.path());
+            }
+            res => panic!("Expected a decode error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_missing_field_path() {
+        let data = value(json!({ "owner": { "name": "Julius" } }));
+
+        match crate::from_value::<BTreeMap<String, Owner>>(data) {
+            Err(Error::DecodeError(e)) => {
+                assert_eq!("/owner", e.path());
+                assert!(e.to_string().contains("nicknames"));
+            }
+            res => panic!("Expected a decode error, got {:?}", res),
+        }
+    }
+}
@@ -0,0 +1,764 @@
+//! A serde `Serializer` for writing Rust types as Fauna expressions.
+use crate::{
+    error::{EncodeError, Error},
+    expr::{Array, Bytes, Expr, Number, Object, Ref},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::ser::{self, Serialize};
+use std::{borrow::Cow, collections::BTreeMap};
+
+pub(crate) const TIMESTAMP: &str = "$faunadb::Timestamp";
+pub(crate) const DATE: &str = "$faunadb::Date";
+pub(crate) const REF: &str = "$faunadb::Ref";
+
+/// Serializes any type implementing `Serialize` into a Fauna `Expr`.
+///
+/// Maps and structs are written as objects, byte arrays as `Bytes`. Fields can
+/// be annotated as timestamps, dates or refs with the helpers in the
+/// [serde](serde/index.html) module.
+///
+/// ```
+/// # use faunadb::prelude::*;
+/// # use chrono::{DateTime, Utc};
+/// #
+/// #[derive(serde_derive::Serialize)]
+/// struct Cat {
+///     name: String,
+///     #[serde(with = "faunadb::serde::timestamp")]
+///     born: DateTime<Utc>,
+/// }
+///
+/// let cat = Cat { name: String::from("Musti"), born: Utc::now() };
+/// let query = Create::new(Class::find("cats"), faunadb::to_expr(&cat).unwrap());
+/// ```
+pub fn to_expr<T>(value: &T) -> crate::Result<Expr<'static>>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(ExprSerializer).map_err(Error::EncodeError)
+}
+
+struct ExprSerializer;
+
+impl ser::Serializer for ExprSerializer {
+    type Ok = Expr<'static>;
+    type Error = EncodeError;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = VariantSerializer<SeqSerializer>;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = VariantSerializer<MapSerializer>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(Number::from(v)))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(Number::from(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(Bytes::from(v.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::null())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::null())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(variant))
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        match name {
+            TIMESTAMP => {
+                let ts: DateTime<Utc> = reparse(value)?;
+                Ok(Expr::from(ts))
+            }
+            DATE => {
+                let date: NaiveDate = reparse(value)?;
+                Ok(Expr::from(date))
+            }
+            REF => {
+                let reference: Ref<'static> = reparse(value)?;
+                Ok(Expr::from(reference))
+            }
+            _ => value.serialize(self),
+        }
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let mut obj = Object::default();
+        obj.0.insert(Cow::from(variant), value.serialize(self)?);
+
+        Ok(Expr::from(obj))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SeqSerializer {
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(MapSerializer {
+            obj: BTreeMap::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(VariantSerializer {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+/// Reads an annotated value from its serialized form.
+fn reparse<T, V>(value: &T) -> Result<V, EncodeError>
+where
+    T: Serialize + ?Sized,
+    V: serde::de::DeserializeOwned,
+{
+    let json = serde_json::to_value(value).map_err(ser::Error::custom)?;
+    serde_json::from_value(json).map_err(ser::Error::custom)
+}
+
+struct SeqSerializer {
+    items: Vec<Expr<'static>>,
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Expr<'static>;
+    type Error = EncodeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.items.push(value.serialize(ExprSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(Array(self.items)))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Expr<'static>;
+    type Error = EncodeError;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Expr<'static>;
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    obj: BTreeMap<Cow<'static, str>, Expr<'static>>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Expr<'static>;
+    type Error = EncodeError;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("serialize_value called before serialize_key"))?;
+
+        self.obj
+            .insert(Cow::from(key), value.serialize(ExprSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Expr::from(Object::from(self.obj)))
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Expr<'static>;
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.obj
+            .insert(Cow::from(key), value.serialize(ExprSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+/// Writes an enum variant with data as an object of one key, the variant
+/// name.
+struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<S> VariantSerializer<S> {
+    fn wrap(variant: &'static str, expr: Expr<'static>) -> Expr<'static> {
+        let mut obj = Object::default();
+        obj.0.insert(Cow::from(variant), expr);
+
+        Expr::from(obj)
+    }
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<SeqSerializer> {
+    type Ok = Expr<'static>;
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        let inner = ser::SerializeSeq::end(self.inner)?;
+        Ok(Self::wrap(self.variant, inner))
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<MapSerializer> {
+    type Ok = Expr<'static>;
+    type Error = EncodeError;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        let inner = ser::SerializeMap::end(self.inner)?;
+        Ok(Self::wrap(self.variant, inner))
+    }
+}
+
+/// Object keys must be strings. Numbers and characters are converted to
+/// strings, other types are rejected.
+struct KeySerializer;
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = EncodeError;
+
+    type SerializeSeq = ser::Impossible<String, EncodeError>;
+    type SerializeTuple = ser::Impossible<String, EncodeError>;
+    type SerializeTupleStruct = ser::Impossible<String, EncodeError>;
+    type SerializeTupleVariant = ser::Impossible<String, EncodeError>;
+    type SerializeMap = ser::Impossible<String, EncodeError>;
+    type SerializeStruct = ser::Impossible<String, EncodeError>;
+    type SerializeStructVariant = ser::Impossible<String, EncodeError>;
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _: bool) -> Result<Self::Ok, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<Self::Ok, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<Self::Ok, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_some<T>(self, _: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(key_must_be_a_string())
+    }
+}
+
+fn key_must_be_a_string() -> EncodeError {
+    ser::Error::custom("object key must be a string")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use chrono::{offset::TimeZone, DateTime, NaiveDate, Utc};
+    use serde_json::{self, json};
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Owner {
+        name: String,
+        nicknames: Vec<&'static str>,
+    }
+
+    #[derive(Serialize)]
+    enum Mood {
+        Happy,
+        Hungry { hours: u32 },
+    }
+
+    #[derive(Serialize)]
+    struct Cat {
+        name: &'static str,
+        age: u8,
+        weight: f64,
+        owner: Owner,
+        friend: Option<String>,
+        #[serde(with = "crate::serde::date")]
+        born: NaiveDate,
+        #[serde(with = "crate::serde::timestamp")]
+        created_at: DateTime<Utc>,
+        #[serde(with = "crate::serde::reference")]
+        home: Ref<'static>,
+        moods: Vec<Mood>,
+        counts: BTreeMap<u32, u32>,
+    }
+
+    #[test]
+    fn test_to_expr() {
+        let mut counts = BTreeMap::new();
+        counts.insert(1, 2);
+
+        let mut home = Ref::instance("1");
+        home.set_class("houses");
+
+        let cat = Cat {
+            name: "Musti",
+            age: 7,
+            weight: 4.5,
+            owner: Owner {
+                name: String::from("Julius"),
+                nicknames: vec!["Pimeys"],
+            },
+            friend: None,
+            born: NaiveDate::from_ymd_opt(2011, 7, 7).unwrap(),
+            created_at: Utc.timestamp_opt(60, 0).unwrap(),
+            home,
+            moods: vec![Mood::Happy, Mood::Hungry { hours: 3 }],
+            counts,
+        };
+
+        let expr = crate::to_expr(&cat).unwrap();
+        let serialized = serde_json::to_value(&expr).unwrap();
+
+        let expected = json!({
+            "object": {
+                "name": "Musti",
+                "age": 7,
+                "weight": 4.5,
+                "owner": {
+                    "object": {
+                        "name": "Julius",
+                        "nicknames": ["Pimeys"]
+                    }
+                },
+                "friend": null,
+                "born": { "@date": "2011-07-07" },
+                "created_at": { "@ts": "1970-01-01T00:01:00Z" },
+                "home": {
+                    "@ref": {
+                        "class": {
+                            "@ref": {
+                                "class": { "@ref": { "id": "classes" } },
+                                "id": "houses"
+                            }
+                        },
+                        "id": "1"
+                    }
+                },
+                "moods": ["Happy", { "object": { "Hungry": { "object": { "hours": 3 } } } }],
+                "counts": { "object": { "1": 2 } },
+            }
+        });
+
+        assert_eq!(expected, serialized);
+    }
+
+    #[test]
+    fn test_bytes() {
+        struct Raw(Vec<u8>);
+
+        impl serde::Serialize for Raw {
+            fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+                s.serialize_bytes(&self.0)
+            }
+        }
+
+        let expr = crate::to_expr(&Raw(vec![0x1, 0x2, 0x3])).unwrap();
+        let serialized = serde_json::to_value(&expr).unwrap();
+
+        assert_eq!(json!({ "@bytes": "AQID" }), serialized);
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Chip {
+            #[serde(with = "crate::serde::bytes")]
+            id: Vec<u8>,
+        }
+
+        let chip = Chip {
+            id: vec![0x1, 0x2, 0x3],
+        };
+
+        let expr = crate::to_expr(&chip).unwrap();
+        let serialized = serde_json::to_value(&expr).unwrap();
+
+        assert_eq!(
+            json!({ "object": { "id": { "@bytes": "AQID" } } }),
+            serialized
+        );
+
+        let value: Value = serde_json::from_value(json!({ "id": { "@bytes": "AQID" } })).unwrap();
+
+        assert_eq!(chip, crate::from_value(value).unwrap());
+    }
+
+    #[test]
+    fn test_in_create() {
+        let mut data = BTreeMap::new();
+        data.insert("name", "Musti");
+
+        let query = Create::new(Ref::class("cats"), crate::to_expr(&data).unwrap());
+        let serialized = serde_json::to_value(&Query::from(query)).unwrap();
+
+        assert_eq!(
+            json!({ "object": { "name": "Musti" } }),
+            serialized["params"]["object"]["data"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let mut data = BTreeMap::new();
+        data.insert(vec![1], "Musti");
+
+        assert!(crate::to_expr(&data).is_err());
+    }
+
+    #[test]
+    fn test_annotations_in_json() {
+        #[derive(Serialize, Deserialize)]
+        struct Dated {
+            #[serde(with = "crate::serde::timestamp")]
+            at: DateTime<Utc>,
+        }
+
+        let dated = Dated {
+            at: Utc.timestamp_opt(60, 0).unwrap(),
+        };
+
+        let json = serde_json::to_value(&dated).unwrap();
+        assert_eq!(json!({ "at": "1970-01-01T00:01:00Z" }), json);
+
+        let value: Value =
+            serde_json::from_value(json!({ "at": { "@ts": "1970-01-01T00:01:00Z" } })).unwrap();
+        let read: Dated = crate::from_value(value).unwrap();
+
+        assert_eq!(dated.at, read.at);
+    }
+}